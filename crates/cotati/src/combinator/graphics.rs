@@ -1,4 +1,4 @@
-use crate::{Angle, Length, Point, Renderer};
+use crate::{rect_radii, Angle, Length, Point, Renderer};
 
 /// Create a circle
 pub fn circle<R, P, L>(center: P, radius: L) -> impl Fn(&mut R) -> Result<(), ()>
@@ -67,6 +67,102 @@ where
     }
 }
 
+/// Create a rectangle.
+pub fn rect<O, W, H, R>(origin: O, width: W, height: H) -> impl Fn(&mut R) -> Result<(), ()>
+where
+    Point: From<O>,
+    Length: From<W> + From<H>,
+    R: Renderer,
+{
+    let origin = origin.into();
+    let width = width.into();
+    let height = height.into();
+
+    move |renderer| {
+        renderer.rect(origin, width, height, None);
+        Ok(())
+    }
+}
+
+/// Create a rectangle with rounded corners.
+///
+/// The radii follow the svg `rect` rules, see [`rect_radii`].
+pub fn rounded_rect<O, W, H, RX, RY, R>(
+    origin: O,
+    width: W,
+    height: H,
+    rx: Option<RX>,
+    ry: Option<RY>,
+) -> impl Fn(&mut R) -> Result<(), ()>
+where
+    Point: From<O>,
+    Length: From<W> + From<H> + From<RX> + From<RY>,
+    R: Renderer,
+{
+    let origin = origin.into();
+    let width: Length = width.into();
+    let height: Length = height.into();
+
+    let radii = rect_radii(width, height, rx.map(Length::from), ry.map(Length::from));
+
+    move |renderer| {
+        renderer.rect(origin, width, height, radii);
+        Ok(())
+    }
+}
+
+/// Create a polyline, a set of connected straight line segments.
+pub fn polyline<I, P, R>(points: I) -> impl Fn(&mut R) -> Result<(), ()>
+where
+    I: IntoIterator<Item = P>,
+    Point: From<P>,
+    R: Renderer,
+{
+    let points = points.into_iter().map(Point::from).collect::<Vec<_>>();
+
+    move |renderer| {
+        lines(renderer, &points, false);
+        Ok(())
+    }
+}
+
+/// Create a polygon, a closed shape consisting of a set of connected straight line segments.
+///
+/// The last point is connected back to the first point automatically.
+pub fn polygon<I, P, R>(points: I) -> impl Fn(&mut R) -> Result<(), ()>
+where
+    I: IntoIterator<Item = P>,
+    Point: From<P>,
+    R: Renderer,
+{
+    let points = points.into_iter().map(Point::from).collect::<Vec<_>>();
+
+    move |renderer| {
+        lines(renderer, &points, true);
+        Ok(())
+    }
+}
+
+/// Emit connected line segments, at least two points are required.
+fn lines<R>(renderer: &mut R, points: &[Point], closed: bool)
+where
+    R: Renderer,
+{
+    if points.len() < 2 {
+        return;
+    }
+
+    renderer.line(Some(points[0]), points[1]);
+
+    for to in &points[2..] {
+        renderer.line(None, *to);
+    }
+
+    if closed {
+        renderer.line(None, points[0]);
+    }
+}
+
 /// Create a quadratic bezier curve.
 pub fn quadratic_bezier<F, C, T, R>(from: F, ctrl: C, to: T) -> impl Fn(&mut R) -> Result<(), ()>
 where
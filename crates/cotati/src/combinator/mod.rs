@@ -15,7 +15,7 @@ pub use label::*;
 mod tests {
 
     use crate::{
-        Angle, Canvas, Length, MeetOrSlice, MockDirection, MockRenderer, Point,
+        Angle, Canvas, Drawing, Length, MeetOrSlice, MockDirection, MockRenderer, Point,
         PreserveAspectRatio, Renderer, Rgba, Unit, ViewBox,
     };
 
@@ -111,4 +111,228 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_polyline() {
+        let mut renderer = MockRenderer::default();
+
+        (
+            polyline([(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]),
+            polygon([(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]),
+            polygon([(0.0, 0.0)]),
+        )
+            .render(&mut renderer)
+            .unwrap();
+
+        assert_eq!(
+            renderer.instructions(),
+            &[
+                MockDirection::Line {
+                    from: Some((0.0, 0.0).into()),
+                    to: (10.0, 0.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (10.0, 10.0).into(),
+                },
+                MockDirection::Line {
+                    from: Some((0.0, 0.0).into()),
+                    to: (10.0, 0.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (10.0, 10.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (0.0, 0.0).into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rect() {
+        let mut renderer = MockRenderer::default();
+
+        (
+            rect((0.0, 0.0), 10.0, 20.0),
+            rounded_rect((0.0, 0.0), 10.0, 20.0, Some(8.0), None::<f32>),
+            rounded_rect((0.0, 0.0), 10.0, 20.0, Some(1.0), Some(30.0)),
+            rounded_rect((0.0, 0.0), 10.0, 20.0, None::<f32>, None::<f32>),
+        )
+            .render(&mut renderer)
+            .unwrap();
+
+        assert_eq!(
+            renderer.instructions(),
+            &[
+                MockDirection::Rect {
+                    origin: (0.0, 0.0).into(),
+                    width: 10.0.into(),
+                    height: 20.0.into(),
+                },
+                MockDirection::RRect {
+                    origin: (0.0, 0.0).into(),
+                    width: 10.0.into(),
+                    height: 20.0.into(),
+                    rx: 5.0.into(),
+                    ry: 8.0.into(),
+                },
+                MockDirection::RRect {
+                    origin: (0.0, 0.0).into(),
+                    width: 10.0.into(),
+                    height: 20.0.into(),
+                    rx: 1.0.into(),
+                    ry: 10.0.into(),
+                },
+                MockDirection::Rect {
+                    origin: (0.0, 0.0).into(),
+                    width: 10.0.into(),
+                    height: 20.0.into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rect_default_impl() {
+        struct SegmentRecorder(MockRenderer);
+
+        impl Renderer for SegmentRecorder {
+            type Error = ();
+            fn clear(&mut self) {}
+            fn pop(&mut self, _: usize) {}
+            fn push_entity(&mut self, _: &str) {}
+            fn push_canvas(&mut self, _: Canvas) {}
+            fn push_path(&mut self) {}
+            fn push_transform(&mut self, _: crate::Transform) {}
+            fn push_fill(&mut self, _: Rgba) {}
+            fn push_stroke(&mut self, _: Rgba, _: Length) {}
+            fn push_label(&mut self, _: &str) {}
+            fn entity_ref(&mut self, _: &str) {}
+            fn move_to(&mut self, _: Point) {}
+            fn line(&mut self, from: Option<Point>, to: Point) {
+                self.0.line(from, to);
+            }
+            fn quadratic_bezier(&mut self, _: Option<Point>, _: Point, _: Point) {}
+            fn cubic_bezier(&mut self, _: Option<Point>, _: Point, _: Point, _: Point) {}
+            fn arc(
+                &mut self,
+                center: Option<Point>,
+                raddii: (Length, Length),
+                start_angle: Angle,
+                sweep_angle: Angle,
+                x_rotation: Angle,
+            ) {
+                self.0
+                    .arc(center, raddii, start_angle, sweep_angle, x_rotation);
+            }
+            fn submit(&mut self) -> Result<(), Self::Error> {
+                Ok(())
+            }
+        }
+
+        let mut renderer = SegmentRecorder(MockRenderer::default());
+
+        rect((0.0, 0.0), 10.0, 20.0)(&mut renderer).unwrap();
+
+        assert_eq!(
+            renderer.0.instructions(),
+            &[
+                MockDirection::Line {
+                    from: Some((0.0, 0.0).into()),
+                    to: (10.0, 0.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (10.0, 20.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (0.0, 20.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (0.0, 0.0).into(),
+                },
+            ]
+        );
+
+        let mut renderer = SegmentRecorder(MockRenderer::default());
+
+        rounded_rect((0.0, 0.0), 10.0, 20.0, Some(2.0), None::<f32>)(&mut renderer).unwrap();
+
+        let arcs = renderer
+            .0
+            .instructions()
+            .iter()
+            .filter(|direction| matches!(direction, MockDirection::Arc { .. }))
+            .count();
+
+        assert_eq!(arcs, 4);
+        assert_eq!(renderer.0.instructions().len(), 8);
+
+        // the radii passed to `Renderer::rect` directly are clamped too.
+        let mut renderer = SegmentRecorder(MockRenderer::default());
+
+        renderer.rect(
+            (0.0, 0.0).into(),
+            10.0.into(),
+            20.0.into(),
+            Some((30.0.into(), 30.0.into())),
+        );
+
+        let raddii = renderer
+            .0
+            .instructions()
+            .iter()
+            .filter_map(|direction| match direction {
+                MockDirection::Arc { raddii, .. } => Some(*raddii),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(raddii, vec![(5.0.into(), 10.0.into()); 4]);
+
+        // a zero radius draws square corners.
+        let mut renderer = SegmentRecorder(MockRenderer::default());
+
+        rounded_rect((0.0, 0.0), 10.0, 20.0, Some(2.0), Some(0.0))(&mut renderer).unwrap();
+
+        assert_eq!(
+            renderer.0.instructions(),
+            &[
+                MockDirection::Line {
+                    from: Some((0.0, 0.0).into()),
+                    to: (10.0, 0.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (10.0, 20.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (0.0, 20.0).into(),
+                },
+                MockDirection::Line {
+                    from: None,
+                    to: (0.0, 0.0).into(),
+                },
+            ]
+        );
+
+        // mismatched units are skipped.
+        let mut renderer = SegmentRecorder(MockRenderer::default());
+
+        rect(Point::mm(0.0, 0.0), Length::cm(1.0), Length::mm(20.0))(&mut renderer).unwrap();
+
+        assert!(renderer.0.instructions().is_empty());
+
+        let mut renderer = SegmentRecorder(MockRenderer::default());
+
+        rect(Point::mm(0.0, 0.0), Length::mm(10.0), Length::mm(20.0))(&mut renderer).unwrap();
+
+        assert_eq!(renderer.0.instructions().len(), 4);
+    }
 }
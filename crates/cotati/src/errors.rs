@@ -1,11 +1,16 @@
 use std::num::{ParseFloatError, ParseIntError};
 
+use crate::Unit;
+
 /// Error variant used by `cotati`.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Invalid viewport string: {0}")]
     InvalidViewPortStr(String),
 
+    #[error("Invalid viewbox string: {0}")]
+    InvalidViewBoxStr(String),
+
     #[error("Unknown color: {0}")]
     UnrecognizedColor(String),
 
@@ -35,6 +40,12 @@ pub enum Error {
 
     #[error("The stack is less than {0}")]
     Pop(usize),
+
+    #[error("Mismatched units: {0:?} and {1:?}")]
+    UnitMismatch(Option<Unit>, Option<Unit>),
+
+    #[error("The unit {0} can't be used in svg path data")]
+    PathUnit(Unit),
 }
 
 /// Result type used by this `cotati`.
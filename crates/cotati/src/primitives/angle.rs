@@ -30,6 +30,38 @@ impl Angle {
     pub fn pi() -> Self {
         Self::deg(180.0)
     }
+
+    /// Returns the angle value in radians.
+    pub fn to_radians(&self) -> f32 {
+        match *self {
+            Angle::deg(v) => v.to_radians(),
+            Angle::grad(v) => v * std::f32::consts::PI / 200.0,
+            Angle::rad(v) => v,
+        }
+    }
+
+    /// Returns the angle value in degrees.
+    pub fn to_degrees(&self) -> f32 {
+        match *self {
+            Angle::deg(v) => v,
+            Angle::grad(v) => v * 0.9,
+            Angle::rad(v) => v.to_degrees(),
+        }
+    }
+
+    /// Returns the equivalent `deg` angle wrapped into `[0,360)`.
+    ///
+    /// Note: a full turn collapses to `0deg`, so don't normalize sweep angles, e.g. the `360deg` sweep of a circle.
+    pub fn normalized(&self) -> Angle {
+        let degrees = self.to_degrees().rem_euclid(360.0);
+
+        // `rem_euclid` may round up to the modulus for tiny negative values.
+        if degrees >= 360.0 {
+            Angle::deg(0.0)
+        } else {
+            Angle::deg(degrees)
+        }
+    }
 }
 
 impl FromStr for Angle {
@@ -77,4 +109,20 @@ mod tests {
 
         assert_eq!("1rad".parse::<Angle>().unwrap(), Angle::rad(1.0));
     }
+
+    #[test]
+    fn normalized() {
+        assert_eq!(Angle::deg(450.0).normalized(), Angle::deg(90.0));
+        assert_eq!(Angle::deg(-90.0).normalized(), Angle::deg(270.0));
+        assert_eq!(Angle::two_pi().normalized(), Angle::zero());
+        assert_eq!(Angle::grad(500.0).normalized(), Angle::deg(90.0));
+
+        assert_eq!(Angle::deg(180.0).to_radians(), std::f32::consts::PI);
+        assert_eq!(Angle::rad(std::f32::consts::PI).to_degrees(), 180.0);
+
+        for degrees in [0.0, 45.0, 90.0, 270.0, 360.0, -30.0] {
+            let radians = Angle::deg(degrees).to_radians();
+            assert!((Angle::rad(radians).to_degrees() - degrees).abs() < 1e-4);
+        }
+    }
 }
@@ -1,5 +1,7 @@
 use std::fmt::Display;
 
+use super::{Transform, ViewBox};
+
 /// see [`https://www.w3.org/TR/SVG11/coords.html#PreserveAspectRatioAttribute`]
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 pub enum MeetOrSlice {
@@ -69,6 +71,11 @@ pub enum PreserveAspectRatio {
     /// Align the `<min-x>`+`<width>` of the element's ‘viewBox’ with the maximum X value of the viewport.
     /// Align the `<min-y>`+`<height>` of the element's ‘viewBox’ with the maximum Y value of the viewport.
     xMaxYMax(MeetOrSlice),
+    /// Do not force uniform scaling.
+    ///
+    /// Scale the graphic content of the given element non-uniformly if necessary such that
+    /// the element's bounding box exactly matches the viewport rectangle.
+    none,
 }
 
 impl Default for PreserveAspectRatio {
@@ -89,6 +96,142 @@ impl Display for PreserveAspectRatio {
             PreserveAspectRatio::xMinYMax(meet_or_slice) => write!(f, "xMinYMax {}", meet_or_slice),
             PreserveAspectRatio::xMidYMax(meet_or_slice) => write!(f, "xMidYMax {}", meet_or_slice),
             PreserveAspectRatio::xMaxYMax(meet_or_slice) => write!(f, "xMaxYMax {}", meet_or_slice),
+            PreserveAspectRatio::none => write!(f, "none"),
+        }
+    }
+}
+
+/// The alignment of one axis.
+#[derive(Clone, Copy)]
+enum Align {
+    Min,
+    Mid,
+    Max,
+}
+
+impl Align {
+    fn offset(self, viewport: f32, content: f32) -> f32 {
+        match self {
+            Align::Min => 0.0,
+            Align::Mid => (viewport - content) / 2.0,
+            Align::Max => viewport - content,
         }
     }
 }
+
+impl PreserveAspectRatio {
+    fn align(&self) -> Option<(Align, Align, MeetOrSlice)> {
+        match *self {
+            PreserveAspectRatio::xMinYMin(m) => Some((Align::Min, Align::Min, m)),
+            PreserveAspectRatio::xMidYMin(m) => Some((Align::Mid, Align::Min, m)),
+            PreserveAspectRatio::xMaxYMin(m) => Some((Align::Max, Align::Min, m)),
+            PreserveAspectRatio::xMinYMid(m) => Some((Align::Min, Align::Mid, m)),
+            PreserveAspectRatio::xMidYMid(m) => Some((Align::Mid, Align::Mid, m)),
+            PreserveAspectRatio::xMaxYMid(m) => Some((Align::Max, Align::Mid, m)),
+            PreserveAspectRatio::xMinYMax(m) => Some((Align::Min, Align::Max, m)),
+            PreserveAspectRatio::xMidYMax(m) => Some((Align::Mid, Align::Max, m)),
+            PreserveAspectRatio::xMaxYMax(m) => Some((Align::Max, Align::Max, m)),
+            PreserveAspectRatio::none => None,
+        }
+    }
+
+    /// Compute the transform that maps `viewbox` user space into a `(width, height)` viewport,
+    /// see [`https://www.w3.org/TR/SVG11/coords.html#PreserveAspectRatioAttribute`].
+    ///
+    /// The lengths of `viewbox` are treated as user units. A `viewbox` with zero
+    /// width or height disables rendering, the result is `scale(0, 0)`.
+    pub fn compute_transform(&self, viewbox: &ViewBox, viewport: (f32, f32)) -> Transform {
+        let (x, y) = (viewbox.x.0, viewbox.y.0);
+        let (width, height) = (viewbox.width.0, viewbox.height.0);
+
+        if width <= 0.0 || height <= 0.0 {
+            return Transform::scale(0.0, 0.0);
+        }
+
+        let (mut sx, mut sy) = (viewport.0 / width, viewport.1 / height);
+
+        let (tx, ty) = match self.align() {
+            Some((align_x, align_y, meet_or_slice)) => {
+                let scale = match meet_or_slice {
+                    MeetOrSlice::Meet => sx.min(sy),
+                    MeetOrSlice::Slice => sx.max(sy),
+                };
+
+                sx = scale;
+                sy = scale;
+
+                (
+                    align_x.offset(viewport.0, width * scale),
+                    align_y.offset(viewport.1, height * scale),
+                )
+            }
+            None => (0.0, 0.0),
+        };
+
+        Transform::Matrix {
+            a: sx,
+            b: 0.0,
+            c: 0.0,
+            d: sy,
+            e: tx - x * sx,
+            f: ty - y * sy,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{MeetOrSlice, PreserveAspectRatio, Transform, ViewBox};
+
+    fn viewbox(x: f32, y: f32, width: f32, height: f32) -> ViewBox {
+        ViewBox {
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
+            aspect: None,
+        }
+    }
+
+    #[test]
+    fn compute_transform() {
+        assert_eq!(
+            PreserveAspectRatio::xMidYMid(MeetOrSlice::Meet)
+                .compute_transform(&viewbox(0.0, 0.0, 10.0, 10.0), (200.0, 100.0)),
+            Transform::Matrix {
+                a: 10.0,
+                b: 0.0,
+                c: 0.0,
+                d: 10.0,
+                e: 50.0,
+                f: 0.0
+            }
+        );
+
+        assert_eq!(
+            PreserveAspectRatio::xMinYMax(MeetOrSlice::Slice)
+                .compute_transform(&viewbox(10.0, 10.0, 10.0, 10.0), (200.0, 100.0)),
+            Transform::Matrix {
+                a: 20.0,
+                b: 0.0,
+                c: 0.0,
+                d: 20.0,
+                e: -200.0,
+                f: -300.0
+            }
+        );
+
+        assert_eq!(
+            PreserveAspectRatio::none
+                .compute_transform(&viewbox(0.0, 0.0, 10.0, 10.0), (200.0, 100.0)),
+            Transform::Matrix {
+                a: 20.0,
+                b: 0.0,
+                c: 0.0,
+                d: 10.0,
+                e: 0.0,
+                f: 0.0
+            }
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use super::{Length, PreserveAspectRatio};
+use crate::{Error, Result};
+
+use super::{Length, Point, PreserveAspectRatio};
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 pub struct ViewBox {
@@ -14,6 +16,48 @@ pub struct ViewBox {
     pub aspect: Option<PreserveAspectRatio>,
 }
 
+impl ViewBox {
+    /// Parse the svg `viewBox` attribute value `<min-x> <min-y> <width> <height>`,
+    /// the numbers are separated by whitespace and/or comma.
+    ///
+    /// A negative or zero `width`/`height` is an error.
+    pub fn parse(s: &str) -> Result<ViewBox> {
+        let values = s
+            .split(|c: char| c.is_ascii_whitespace() || c == ',')
+            .filter(|v| !v.is_empty())
+            .map(|v| v.parse::<f32>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| Error::InvalidViewBoxStr(s.to_string()))?;
+
+        let &[x, y, width, height] = values.as_slice() else {
+            return Err(Error::InvalidViewBoxStr(s.to_string()));
+        };
+
+        if !(width > 0.0 && height > 0.0) {
+            return Err(Error::InvalidViewBoxStr(s.to_string()));
+        }
+
+        Ok(ViewBox {
+            x: x.into(),
+            y: y.into(),
+            width: width.into(),
+            height: height.into(),
+            aspect: None,
+        })
+    }
+
+    /// Map point `p` from this viewbox user space into a `(width, height)` viewport,
+    /// see [`PreserveAspectRatio::compute_transform`].
+    pub fn to_viewport(
+        &self,
+        p: Point,
+        viewport: (f32, f32),
+        aspect: PreserveAspectRatio,
+    ) -> Point {
+        aspect.compute_transform(self, viewport).apply_point(p)
+    }
+}
+
 /// A canvas configuration.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 pub struct Canvas {
@@ -38,3 +82,59 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, MeetOrSlice, PreserveAspectRatio, ViewBox};
+
+    #[test]
+    fn parse() {
+        let viewbox = ViewBox::parse("0 0 100 100").unwrap();
+
+        assert_eq!(
+            viewbox,
+            ViewBox {
+                x: 0.0.into(),
+                y: 0.0.into(),
+                width: 100.0.into(),
+                height: 100.0.into(),
+                aspect: None,
+            }
+        );
+
+        assert_eq!(ViewBox::parse(" -10,5  20, 30").unwrap().x, (-10.0).into());
+
+        ViewBox::parse("0 0 100").expect_err("missing height");
+        ViewBox::parse("0 0 0 100").expect_err("zero width");
+        ViewBox::parse("0 0 100 -1").expect_err("negative height");
+        ViewBox::parse("0 0 100 a").expect_err("invalid number");
+
+        assert!(matches!(
+            ViewBox::parse("0 0 100"),
+            Err(Error::InvalidViewBoxStr(s)) if s == "0 0 100"
+        ));
+    }
+
+    #[test]
+    fn to_viewport() {
+        let viewbox = ViewBox::parse("0 0 100 100").unwrap();
+
+        assert_eq!(
+            viewbox.to_viewport(
+                (50.0, 50.0).into(),
+                (400.0, 200.0),
+                PreserveAspectRatio::xMidYMid(MeetOrSlice::Meet)
+            ),
+            (200.0, 100.0).into()
+        );
+
+        assert_eq!(
+            viewbox.to_viewport(
+                (0.0, 0.0).into(),
+                (400.0, 200.0),
+                PreserveAspectRatio::xMidYMid(MeetOrSlice::Meet)
+            ),
+            (100.0, 0.0).into()
+        );
+    }
+}
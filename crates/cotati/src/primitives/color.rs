@@ -4,6 +4,7 @@ use nom::{
     branch::alt,
     bytes::complete::{tag, tag_no_case, take_while},
     character::complete::hex_digit1,
+    number::complete::recognize_float,
     IResult,
 };
 
@@ -19,9 +20,16 @@ impl From<Rgba> for [f32; 4] {
     }
 }
 
+/// Display as `rgba(<r>,<g>,<b>,<a>)` with normalized float components,
+/// the output can be parsed back via [`FromStr`].
 impl Display for Rgba {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "rgba({},{},{},{})", self.0, self.1, self.2, self.3)
+        // `{:?}` always keeps the decimal point, so the output isn't misclassified as 0-255 integers.
+        write!(
+            f,
+            "rgba({:?},{:?},{:?},{:?})",
+            self.0, self.1, self.2, self.3
+        )
     }
 }
 
@@ -50,6 +58,134 @@ impl Rgba {
             alpha as f32 / 255f32,
         )
     }
+
+    /// Returns the normalized red channel.
+    pub const fn red(&self) -> f32 {
+        self.0
+    }
+
+    /// Returns the normalized green channel.
+    pub const fn green(&self) -> f32 {
+        self.1
+    }
+
+    /// Returns the normalized blue channel.
+    pub const fn blue(&self) -> f32 {
+        self.2
+    }
+
+    /// Returns the normalized alpha channel.
+    pub const fn alpha(&self) -> f32 {
+        self.3
+    }
+
+    /// Returns a copy of this color with the normalized alpha channel replaced by `alpha`.
+    pub const fn with_alpha(self, alpha: f32) -> Rgba {
+        Self(self.0, self.1, self.2, alpha)
+    }
+
+    /// Returns the [`Recognized`] color keyword exactly matching this color.
+    ///
+    /// The channels are compared after quantizing to `u8`, a transparent color never matches.
+    pub fn exact_recognized(&self) -> Option<Recognized> {
+        let quantize = |rgba: Rgba| -> [u8; 4] {
+            [rgba.0, rgba.1, rgba.2, rgba.3].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+        };
+
+        let this = quantize(*self);
+
+        RECOGNIZED
+            .iter()
+            .copied()
+            .find(|recognized| quantize((*recognized).into()) == this)
+    }
+
+    /// Returns the [`Recognized`] color keyword with the minimum euclidean distance in rgb space.
+    ///
+    /// The alpha channel is ignored, for aliases (e.g. `gray`/`grey`) the first declared keyword is returned.
+    pub fn nearest_recognized(&self) -> Recognized {
+        let distance = |recognized: Recognized| {
+            let rgba: Rgba = recognized.into();
+
+            (rgba.0 - self.0).powi(2) + (rgba.1 - self.1).powi(2) + (rgba.2 - self.2).powi(2)
+        };
+
+        let mut nearest = RECOGNIZED[0];
+        let mut min_distance = distance(nearest);
+
+        for recognized in &RECOGNIZED[1..] {
+            let distance = distance(*recognized);
+
+            if distance < min_distance {
+                nearest = *recognized;
+                min_distance = distance;
+            }
+        }
+
+        nearest
+    }
+
+    /// Linearly interpolate between `self` and `other` on each normalized channel.
+    ///
+    /// `t` is clamped to `[0, 1]`, `0` returns `self` and `1` returns `other`.
+    pub fn lerp(self, other: Rgba, t: f32) -> Rgba {
+        let t = t.clamp(0.0, 1.0);
+
+        Rgba(
+            self.0 + (other.0 - self.0) * t,
+            self.1 + (other.1 - self.1) * t,
+            self.2 + (other.2 - self.2) * t,
+            self.3 + (other.3 - self.3) * t,
+        )
+    }
+
+    /// Linearly interpolate between `self` and `other` in premultiplied-alpha space.
+    ///
+    /// Unlike [`lerp`](Rgba::lerp), the color of a transparent stop doesn't bleed into the result.
+    pub fn lerp_premultiplied(self, other: Rgba, t: f32) -> Rgba {
+        let t = t.clamp(0.0, 1.0);
+
+        let premultiply = |c: Rgba| [c.0 * c.3, c.1 * c.3, c.2 * c.3];
+
+        let from = premultiply(self);
+        let to = premultiply(other);
+
+        let alpha = self.3 + (other.3 - self.3) * t;
+
+        if alpha == 0.0 {
+            return Rgba(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let [r, g, b] = [0, 1, 2].map(|i| (from[i] + (to[i] - from[i]) * t) / alpha);
+
+        Rgba(r, g, b, alpha)
+    }
+
+    /// Create a `Rgba` from hsl values with opaque alpha channel.
+    ///
+    /// `hue` is in degrees, `saturation` and `lightness` are normalized values.
+    pub fn hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        Self::hsla(hue, saturation, lightness, 1.0)
+    }
+
+    /// Create a `Rgba` from hsla values.
+    ///
+    /// `hue` is in degrees, `saturation`, `lightness` and `alpha` are normalized values.
+    pub fn hsla(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        // see https://www.w3.org/TR/css-color-4/#hsl-to-rgb
+        let h = hue.rem_euclid(360.0);
+        let s = saturation.clamp(0.0, 1.0);
+        let l = lightness.clamp(0.0, 1.0);
+
+        let a = s * l.min(1.0 - l);
+
+        let f = |n: f32| {
+            let k = (n + h / 30.0) % 12.0;
+            l - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+        };
+
+        Self::newf(f(0.0), f(8.0), f(4.0), alpha)
+    }
 }
 
 impl FromStr for Rgba {
@@ -66,55 +202,44 @@ impl FromStr for Rgba {
             let (input, _) = tag("#")(input)?;
             let (input, hex) = hex_digit1(input)?;
 
-            match hex.len() {
-                3 => {
-                    let value = format!(
-                        "{}{}{}{}{}{}",
-                        &hex[0..1],
-                        &hex[0..1],
-                        &hex[1..2],
-                        &hex[1..2],
-                        &hex[2..],
-                        &hex[2..],
-                    );
-
-                    let hex = u32::from_str_radix(&value, 16).map_err(|_| {
-                        nom::Err::Failure(nom::error::Error::new(
-                            input,
-                            nom::error::ErrorKind::HexDigit,
-                        ))
-                    })?;
-
-                    Ok((
-                        input,
-                        Rgba::rgb(
-                            ((hex >> 16) & 0xff) as u8,
-                            ((hex >> 8) & 0xff) as u8,
-                            (hex & 0xff) as u8,
-                        ),
-                    ))
-                }
-                6 => {
-                    let hex = u32::from_str_radix(hex, 16).map_err(|_| {
-                        nom::Err::Failure(nom::error::Error::new(
-                            input,
-                            nom::error::ErrorKind::HexDigit,
-                        ))
-                    })?;
-
-                    Ok((
+            // expand the short form `#rgb`/`#rgba` by doubling each nibble.
+            let hex = match hex.len() {
+                3 | 4 => hex.chars().flat_map(|c| [c, c]).collect::<String>(),
+                6 | 8 => hex.to_owned(),
+                _ => {
+                    return Err(nom::Err::Failure(nom::error::Error::new(
                         input,
-                        Rgba::rgb(
-                            ((hex >> 16) & 0xff) as u8,
-                            ((hex >> 8) & 0xff) as u8,
-                            (hex & 0xff) as u8,
-                        ),
-                    ))
+                        nom::error::ErrorKind::HexDigit,
+                    )))
                 }
-                _ => Err(nom::Err::Failure(nom::error::Error::new(
+            };
+
+            let value = u32::from_str_radix(&hex, 16).map_err(|_| {
+                nom::Err::Failure(nom::error::Error::new(
                     input,
                     nom::error::ErrorKind::HexDigit,
-                ))),
+                ))
+            })?;
+
+            if hex.len() == 8 {
+                Ok((
+                    input,
+                    Rgba::new(
+                        ((value >> 24) & 0xff) as u8,
+                        ((value >> 16) & 0xff) as u8,
+                        ((value >> 8) & 0xff) as u8,
+                        (value & 0xff) as u8,
+                    ),
+                ))
+            } else {
+                Ok((
+                    input,
+                    Rgba::rgb(
+                        ((value >> 16) & 0xff) as u8,
+                        ((value >> 8) & 0xff) as u8,
+                        (value & 0xff) as u8,
+                    ),
+                ))
             }
         }
 
@@ -167,7 +292,111 @@ impl FromStr for Rgba {
             ))
         }
 
-        let (_, rgb) = alt((rgbhex, rgb, rgb_percentage))(s)
+        fn alpha(input: &str) -> IResult<&str, f32> {
+            let (input, alpha) = nom::number::complete::float(input)?;
+
+            if !(0.0..=1.0).contains(&alpha) {
+                return Err(nom::Err::Failure(nom::error::Error::new(
+                    input,
+                    nom::error::ErrorKind::Float,
+                )));
+            }
+
+            Ok((input, alpha))
+        }
+
+        /// Parse `rgba(r,g,b,a)`, each rgb component is either a 0-255 integer
+        /// or a normalized float with a decimal point or exponent (the [`Display`] form).
+        fn rgba(input: &str) -> IResult<&str, Rgba> {
+            let (input, _) = tag_no_case("rgba(")(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, r) = recognize_float(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, _) = tag(",")(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, g) = recognize_float(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, _) = tag(",")(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, b) = recognize_float(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, _) = tag(",")(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, a) = alpha(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, _) = tag(")")(input)?;
+
+            let failure =
+                || nom::Err::Failure(nom::error::Error::new(input, nom::error::ErrorKind::Float));
+
+            let mut rgb = [0f32; 3];
+
+            for (value, s) in rgb.iter_mut().zip([r, g, b]) {
+                // plain integers are 0-255 values, otherwise normalized floats.
+                if s.contains(['.', 'e', 'E']) {
+                    *value = s.parse::<f32>().map_err(|_| failure())?;
+
+                    if !(0.0..=1.0).contains(value) {
+                        return Err(failure());
+                    }
+                } else {
+                    *value = s.parse::<u8>().map_err(|_| failure())? as f32 / 255f32;
+                }
+            }
+
+            Ok((input, Rgba::newf(rgb[0], rgb[1], rgb[2], a)))
+        }
+
+        fn hsl_or_hsla(input: &str, has_alpha: bool) -> IResult<&str, Rgba> {
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, h) = nom::number::complete::float(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, _) = tag(",")(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, s) = nom::number::complete::float(input)?;
+            let (input, _) = tag("%")(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, _) = tag(",")(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+            let (input, l) = nom::number::complete::float(input)?;
+            let (input, _) = tag("%")(input)?;
+            let (input, _) = take_while(is_whitespace_char)(input)?;
+
+            let (input, a) = if has_alpha {
+                let (input, _) = tag(",")(input)?;
+                let (input, _) = take_while(is_whitespace_char)(input)?;
+                let (input, a) = alpha(input)?;
+                let (input, _) = take_while(is_whitespace_char)(input)?;
+                (input, a)
+            } else {
+                (input, 1.0)
+            };
+
+            let (input, _) = tag(")")(input)?;
+
+            if !(0.0..=100.0).contains(&s) || !(0.0..=100.0).contains(&l) {
+                return Err(nom::Err::Failure(nom::error::Error::new(
+                    input,
+                    nom::error::ErrorKind::AlphaNumeric,
+                )));
+            }
+
+            Ok((input, Rgba::hsla(h, s / 100f32, l / 100f32, a)))
+        }
+
+        fn hsl(input: &str) -> IResult<&str, Rgba> {
+            let (input, _) = tag_no_case("hsl(")(input)?;
+
+            hsl_or_hsla(input, false)
+        }
+
+        fn hsla(input: &str) -> IResult<&str, Rgba> {
+            let (input, _) = tag_no_case("hsla(")(input)?;
+
+            hsl_or_hsla(input, true)
+        }
+
+        let (_, rgb) = alt((rgbhex, rgb, rgb_percentage, rgba, hsl, hsla))(s)
             .map_err(|_| Error::UnrecognizedColor(s.to_owned()))?;
 
         Ok(rgb)
@@ -212,474 +441,195 @@ impl From<(u8, u8, u8, u8)> for Rgba {
     }
 }
 
-/// Recognized color keyword names, compliant with svg 1.1.
-#[allow(non_camel_case_types)]
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum Recognized {
-    aliceblue,
-    antiquewhite,
-    aqua,
-    aquamarine,
-    azure,
-    beige,
-    bisque,
-    black,
-    blanchedalmond,
-    blue,
-    blueviolet,
-    brown,
-    burlywood,
-    cadetblue,
-    chartreuse,
-    chocolate,
-    coral,
-    cornflowerblue,
-    cornsilk,
-    crimson,
-    cyan,
-    darkblue,
-    darkcyan,
-    darkgoldenrod,
-    darkgray,
-    darkgreen,
-    darkgrey,
-    darkkhaki,
-    darkmagenta,
-    darkolivegreen,
-    darkorange,
-    darkorchid,
-    darkred,
-    darksalmon,
-    darkseagreen,
-    darkslateblue,
-    darkslategray,
-    darkslategrey,
-    darkturquoise,
-    darkviolet,
-    deeppink,
-    deepskyblue,
-    dimgray,
-    dimgrey,
-    dodgerblue,
-    firebrick,
-    floralwhite,
-    forestgreen,
-    fuchsia,
-    gainsboro,
-    ghostwhite,
-    gold,
-    goldenrod,
-    gray,
-    grey,
-    green,
-    greenyellow,
-    honeydew,
-    hotpink,
-    indianred,
-    indigo,
-    ivory,
-    khaki,
-    lavender,
-    lavenderblush,
-    lawngreen,
-    lemonchiffon,
-    lightblue,
-    lightcoral,
-    lightcyan,
-    lightgoldenrodyellow,
-    lightgray,
-    lightgreen,
-    lightgrey,
-    lightpink,
-    lightsalmon,
-    lightseagreen,
-    lightskyblue,
-    lightslategray,
-    lightslategrey,
-    lightsteelblue,
-    lightyellow,
-    lime,
-    limegreen,
-    linen,
-    magenta,
-    maroon,
-    mediumaquamarine,
-    mediumblue,
-    mediumorchid,
-    mediumpurple,
-    mediumseagreen,
-    mediumslateblue,
-    mediumspringgreen,
-    mediumturquoise,
-    mediumvioletred,
-    midnightblue,
-    mintcream,
-    mistyrose,
-    moccasin,
-    navajowhite,
-    navy,
-    oldlace,
-    olive,
-    olivedrab,
-    orange,
-    orangered,
-    orchid,
-    palegoldenrod,
-    palegreen,
-    paleturquoise,
-    palevioletred,
-    papayawhip,
-    peachpuff,
-    peru,
-    pink,
-    plum,
-    powderblue,
-    purple,
-    red,
-    rosybrown,
-    royalblue,
-    saddlebrown,
-    salmon,
-    sandybrown,
-    seagreen,
-    seashell,
-    sienna,
-    silver,
-    skyblue,
-    slateblue,
-    slategray,
-    slategrey,
-    snow,
-    springgreen,
-    steelblue,
-    tan,
-    teal,
-    thistle,
-    tomato,
-    turquoise,
-    violet,
-    wheat,
-    white,
-    whitesmoke,
-    yellow,
-    yellowgreen,
-}
+/// Declare the [`Recognized`] enum, the `RECOGNIZED` list and the keyword/rgb conversions from one table,
+/// so they can't drift apart.
+macro_rules! recognized {
+    ($($name: ident => ($r: literal, $g: literal, $b: literal)),* $(,)?) => {
+        /// Recognized color keyword names, compliant with svg 1.1.
+        #[allow(non_camel_case_types)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+        pub enum Recognized {
+            $($name),*
+        }
 
-impl FromStr for Recognized {
-    type Err = crate::Error;
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "aliceblue" => Ok(Self::aliceblue),
-            "antiquewhite" => Ok(Self::antiquewhite),
-            "aqua" => Ok(Self::aqua),
-            "aquamarine" => Ok(Self::aquamarine),
-            "azure" => Ok(Self::azure),
-            "beige" => Ok(Self::beige),
-            "bisque" => Ok(Self::bisque),
-            "black" => Ok(Self::black),
-            "blanchedalmond" => Ok(Self::blanchedalmond),
-            "blue" => Ok(Self::blue),
-            "blueviolet" => Ok(Self::blueviolet),
-            "brown" => Ok(Self::brown),
-            "burlywood" => Ok(Self::burlywood),
-            "cadetblue" => Ok(Self::cadetblue),
-            "chartreuse" => Ok(Self::chartreuse),
-            "chocolate" => Ok(Self::chocolate),
-            "coral" => Ok(Self::coral),
-            "cornflowerblue" => Ok(Self::cornflowerblue),
-            "cornsilk" => Ok(Self::cornsilk),
-            "crimson" => Ok(Self::crimson),
-            "cyan" => Ok(Self::cyan),
-            "darkblue" => Ok(Self::darkblue),
-            "darkcyan" => Ok(Self::darkcyan),
-            "darkgoldenrod" => Ok(Self::darkgoldenrod),
-            "darkgray" => Ok(Self::darkgray),
-            "darkgreen" => Ok(Self::darkgreen),
-            "darkgrey" => Ok(Self::darkgrey),
-            "darkkhaki" => Ok(Self::darkkhaki),
-            "darkmagenta" => Ok(Self::darkmagenta),
-            "darkolivegreen" => Ok(Self::darkolivegreen),
-            "darkorange" => Ok(Self::darkorange),
-            "darkorchid" => Ok(Self::darkorchid),
-            "darkred" => Ok(Self::darkred),
-            "darksalmon" => Ok(Self::darksalmon),
-            "darkseagreen" => Ok(Self::darkseagreen),
-            "darkslateblue" => Ok(Self::darkslateblue),
-            "darkslategray" => Ok(Self::darkslategray),
-            "darkslategrey" => Ok(Self::darkslategrey),
-            "darkturquoise" => Ok(Self::darkturquoise),
-            "darkviolet" => Ok(Self::darkviolet),
-            "deeppink" => Ok(Self::deeppink),
-            "deepskyblue" => Ok(Self::deepskyblue),
-            "dimgray" => Ok(Self::dimgray),
-            "dimgrey" => Ok(Self::dimgrey),
-            "dodgerblue" => Ok(Self::dodgerblue),
-            "firebrick" => Ok(Self::firebrick),
-            "floralwhite" => Ok(Self::floralwhite),
-            "forestgreen" => Ok(Self::forestgreen),
-            "fuchsia" => Ok(Self::fuchsia),
-            "gainsboro" => Ok(Self::gainsboro),
-            "ghostwhite" => Ok(Self::ghostwhite),
-            "gold" => Ok(Self::gold),
-            "goldenrod" => Ok(Self::goldenrod),
-            "gray" => Ok(Self::gray),
-            "grey" => Ok(Self::grey),
-            "green" => Ok(Self::green),
-            "greenyellow" => Ok(Self::greenyellow),
-            "honeydew" => Ok(Self::honeydew),
-            "hotpink" => Ok(Self::hotpink),
-            "indianred" => Ok(Self::indianred),
-            "indigo" => Ok(Self::indigo),
-            "ivory" => Ok(Self::ivory),
-            "khaki" => Ok(Self::khaki),
-            "lavender" => Ok(Self::lavender),
-            "lavenderblush" => Ok(Self::lavenderblush),
-            "lawngreen" => Ok(Self::lawngreen),
-            "lemonchiffon" => Ok(Self::lemonchiffon),
-            "lightblue" => Ok(Self::lightblue),
-            "lightcoral" => Ok(Self::lightcoral),
-            "lightcyan" => Ok(Self::lightcyan),
-            "lightgoldenrodyellow" => Ok(Self::lightgoldenrodyellow),
-            "lightgray" => Ok(Self::lightgray),
-            "lightgreen" => Ok(Self::lightgreen),
-            "lightgrey" => Ok(Self::lightgrey),
-            "lightpink" => Ok(Self::lightpink),
-            "lightsalmon" => Ok(Self::lightsalmon),
-            "lightseagreen" => Ok(Self::lightseagreen),
-            "lightskyblue" => Ok(Self::lightskyblue),
-            "lightslategray" => Ok(Self::lightslategray),
-            "lightslategrey" => Ok(Self::lightslategrey),
-            "lightsteelblue" => Ok(Self::lightsteelblue),
-            "lightyellow" => Ok(Self::lightyellow),
-            "lime" => Ok(Self::lime),
-            "limegreen" => Ok(Self::limegreen),
-            "linen" => Ok(Self::linen),
-            "magenta" => Ok(Self::magenta),
-            "maroon" => Ok(Self::maroon),
-            "mediumaquamarine" => Ok(Self::mediumaquamarine),
-            "mediumblue" => Ok(Self::mediumblue),
-            "mediumorchid" => Ok(Self::mediumorchid),
-            "mediumpurple" => Ok(Self::mediumpurple),
-            "mediumseagreen" => Ok(Self::mediumseagreen),
-            "mediumslateblue" => Ok(Self::mediumslateblue),
-            "mediumspringgreen" => Ok(Self::mediumspringgreen),
-            "mediumturquoise" => Ok(Self::mediumturquoise),
-            "mediumvioletred" => Ok(Self::mediumvioletred),
-            "midnightblue" => Ok(Self::midnightblue),
-            "mintcream" => Ok(Self::mintcream),
-            "mistyrose" => Ok(Self::mistyrose),
-            "moccasin" => Ok(Self::moccasin),
-            "navajowhite" => Ok(Self::navajowhite),
-            "navy" => Ok(Self::navy),
-            "oldlace" => Ok(Self::oldlace),
-            "olive" => Ok(Self::olive),
-            "olivedrab" => Ok(Self::olivedrab),
-            "orange" => Ok(Self::orange),
-            "orangered" => Ok(Self::orangered),
-            "orchid" => Ok(Self::orchid),
-            "palegoldenrod" => Ok(Self::palegoldenrod),
-            "palegreen" => Ok(Self::palegreen),
-            "paleturquoise" => Ok(Self::paleturquoise),
-            "palevioletred" => Ok(Self::palevioletred),
-            "papayawhip" => Ok(Self::papayawhip),
-            "peachpuff" => Ok(Self::peachpuff),
-            "peru" => Ok(Self::peru),
-            "pink" => Ok(Self::pink),
-            "plum" => Ok(Self::plum),
-            "powderblue" => Ok(Self::powderblue),
-            "purple" => Ok(Self::purple),
-            "red" => Ok(Self::red),
-            "rosybrown" => Ok(Self::rosybrown),
-            "royalblue" => Ok(Self::royalblue),
-            "saddlebrown" => Ok(Self::saddlebrown),
-            "salmon" => Ok(Self::salmon),
-            "sandybrown" => Ok(Self::sandybrown),
-            "seagreen" => Ok(Self::seagreen),
-            "seashell" => Ok(Self::seashell),
-            "sienna" => Ok(Self::sienna),
-            "silver" => Ok(Self::silver),
-            "skyblue" => Ok(Self::skyblue),
-            "slateblue" => Ok(Self::slateblue),
-            "slategray" => Ok(Self::slategray),
-            "slategrey" => Ok(Self::slategrey),
-            "snow" => Ok(Self::snow),
-            "springgreen" => Ok(Self::springgreen),
-            "steelblue" => Ok(Self::steelblue),
-            "tan" => Ok(Self::tan),
-            "teal" => Ok(Self::teal),
-            "thistle" => Ok(Self::thistle),
-            "tomato" => Ok(Self::tomato),
-            "turquoise" => Ok(Self::turquoise),
-            "violet" => Ok(Self::violet),
-            "wheat" => Ok(Self::wheat),
-            "white" => Ok(Self::white),
-            "whitesmoke" => Ok(Self::whitesmoke),
-            "yellow" => Ok(Self::yellow),
-            "yellowgreen" => Ok(Self::yellowgreen),
-            _ => return Err(crate::Error::UnrecognizedColor(s.to_owned())),
+        /// All [`Recognized`] colors, in declaration order.
+        const RECOGNIZED: &[Recognized] = &[$(Recognized::$name),*];
+
+        impl FromStr for Recognized {
+            type Err = crate::Error;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $(stringify!($name) => Ok(Self::$name),)*
+                    _ => Err(crate::Error::UnrecognizedColor(s.to_owned())),
+                }
+            }
         }
-    }
-}
 
-impl From<Recognized> for Rgba {
-    fn from(value: Recognized) -> Self {
-        match value {
-            Recognized::aliceblue => Rgba::rgb(240, 248, 255),
-            Recognized::antiquewhite => Rgba::rgb(250, 235, 215),
-            Recognized::aqua => Rgba::rgb(0, 255, 255),
-            Recognized::aquamarine => Rgba::rgb(127, 255, 212),
-            Recognized::azure => Rgba::rgb(240, 255, 255),
-            Recognized::beige => Rgba::rgb(245, 245, 220),
-            Recognized::bisque => Rgba::rgb(255, 228, 196),
-            Recognized::black => Rgba::rgb(0, 0, 0),
-            Recognized::blanchedalmond => Rgba::rgb(255, 235, 205),
-            Recognized::blue => Rgba::rgb(0, 0, 255),
-            Recognized::blueviolet => Rgba::rgb(138, 43, 226),
-            Recognized::brown => Rgba::rgb(165, 42, 42),
-            Recognized::burlywood => Rgba::rgb(222, 184, 135),
-            Recognized::cadetblue => Rgba::rgb(95, 158, 160),
-            Recognized::chartreuse => Rgba::rgb(127, 255, 0),
-            Recognized::chocolate => Rgba::rgb(210, 105, 30),
-            Recognized::coral => Rgba::rgb(255, 127, 80),
-            Recognized::cornflowerblue => Rgba::rgb(100, 149, 237),
-            Recognized::cornsilk => Rgba::rgb(255, 248, 220),
-            Recognized::crimson => Rgba::rgb(220, 20, 60),
-            Recognized::cyan => Rgba::rgb(0, 255, 255),
-            Recognized::darkblue => Rgba::rgb(0, 0, 139),
-            Recognized::darkcyan => Rgba::rgb(0, 139, 139),
-            Recognized::darkgoldenrod => Rgba::rgb(184, 134, 11),
-            Recognized::darkgray => Rgba::rgb(169, 169, 169),
-            Recognized::darkgreen => Rgba::rgb(0, 100, 0),
-            Recognized::darkgrey => Rgba::rgb(169, 169, 169),
-            Recognized::darkkhaki => Rgba::rgb(189, 183, 107),
-            Recognized::darkmagenta => Rgba::rgb(139, 0, 139),
-            Recognized::darkolivegreen => Rgba::rgb(85, 107, 47),
-            Recognized::darkorange => Rgba::rgb(255, 140, 0),
-            Recognized::darkorchid => Rgba::rgb(153, 50, 204),
-            Recognized::darkred => Rgba::rgb(139, 0, 0),
-            Recognized::darksalmon => Rgba::rgb(233, 150, 122),
-            Recognized::darkseagreen => Rgba::rgb(143, 188, 143),
-            Recognized::darkslateblue => Rgba::rgb(72, 61, 139),
-            Recognized::darkslategray => Rgba::rgb(47, 79, 79),
-            Recognized::darkslategrey => Rgba::rgb(47, 79, 79),
-            Recognized::darkturquoise => Rgba::rgb(0, 206, 209),
-            Recognized::darkviolet => Rgba::rgb(148, 0, 211),
-            Recognized::deeppink => Rgba::rgb(255, 20, 147),
-            Recognized::deepskyblue => Rgba::rgb(0, 191, 255),
-            Recognized::dimgray => Rgba::rgb(105, 105, 105),
-            Recognized::dimgrey => Rgba::rgb(105, 105, 105),
-            Recognized::dodgerblue => Rgba::rgb(30, 144, 255),
-            Recognized::firebrick => Rgba::rgb(178, 34, 34),
-            Recognized::floralwhite => Rgba::rgb(255, 250, 240),
-            Recognized::forestgreen => Rgba::rgb(34, 139, 34),
-            Recognized::fuchsia => Rgba::rgb(255, 0, 255),
-            Recognized::gainsboro => Rgba::rgb(220, 220, 220),
-            Recognized::ghostwhite => Rgba::rgb(248, 248, 255),
-            Recognized::gold => Rgba::rgb(255, 215, 0),
-            Recognized::goldenrod => Rgba::rgb(218, 165, 32),
-            Recognized::gray => Rgba::rgb(128, 128, 128),
-            Recognized::grey => Rgba::rgb(128, 128, 128),
-            Recognized::green => Rgba::rgb(0, 128, 0),
-            Recognized::greenyellow => Rgba::rgb(173, 255, 47),
-            Recognized::honeydew => Rgba::rgb(240, 255, 240),
-            Recognized::hotpink => Rgba::rgb(255, 105, 180),
-            Recognized::indianred => Rgba::rgb(205, 92, 92),
-            Recognized::indigo => Rgba::rgb(75, 0, 130),
-            Recognized::ivory => Rgba::rgb(255, 255, 240),
-            Recognized::khaki => Rgba::rgb(240, 230, 140),
-            Recognized::lavender => Rgba::rgb(230, 230, 250),
-            Recognized::lavenderblush => Rgba::rgb(255, 240, 245),
-            Recognized::lawngreen => Rgba::rgb(124, 252, 0),
-            Recognized::lemonchiffon => Rgba::rgb(255, 250, 205),
-            Recognized::lightblue => Rgba::rgb(173, 216, 230),
-            Recognized::lightcoral => Rgba::rgb(240, 128, 128),
-            Recognized::lightcyan => Rgba::rgb(224, 255, 255),
-            Recognized::lightgoldenrodyellow => Rgba::rgb(250, 250, 210),
-            Recognized::lightgray => Rgba::rgb(211, 211, 211),
-            Recognized::lightgreen => Rgba::rgb(144, 238, 144),
-            Recognized::lightgrey => Rgba::rgb(211, 211, 211),
-            Recognized::lightpink => Rgba::rgb(255, 182, 193),
-            Recognized::lightsalmon => Rgba::rgb(255, 160, 122),
-            Recognized::lightseagreen => Rgba::rgb(32, 178, 170),
-            Recognized::lightskyblue => Rgba::rgb(135, 206, 250),
-            Recognized::lightslategray => Rgba::rgb(119, 136, 153),
-            Recognized::lightslategrey => Rgba::rgb(119, 136, 153),
-            Recognized::lightsteelblue => Rgba::rgb(176, 196, 222),
-            Recognized::lightyellow => Rgba::rgb(255, 255, 224),
-            Recognized::lime => Rgba::rgb(0, 255, 0),
-            Recognized::limegreen => Rgba::rgb(50, 205, 50),
-            Recognized::linen => Rgba::rgb(250, 240, 230),
-            Recognized::magenta => Rgba::rgb(255, 0, 255),
-            Recognized::maroon => Rgba::rgb(128, 0, 0),
-            Recognized::mediumaquamarine => Rgba::rgb(102, 205, 170),
-            Recognized::mediumblue => Rgba::rgb(0, 0, 205),
-            Recognized::mediumorchid => Rgba::rgb(186, 85, 211),
-            Recognized::mediumpurple => Rgba::rgb(147, 112, 219),
-            Recognized::mediumseagreen => Rgba::rgb(60, 179, 113),
-            Recognized::mediumslateblue => Rgba::rgb(123, 104, 238),
-            Recognized::mediumspringgreen => Rgba::rgb(0, 250, 154),
-            Recognized::mediumturquoise => Rgba::rgb(72, 209, 204),
-            Recognized::mediumvioletred => Rgba::rgb(199, 21, 133),
-            Recognized::midnightblue => Rgba::rgb(25, 25, 112),
-            Recognized::mintcream => Rgba::rgb(245, 255, 250),
-            Recognized::mistyrose => Rgba::rgb(255, 228, 225),
-            Recognized::moccasin => Rgba::rgb(255, 228, 181),
-            Recognized::navajowhite => Rgba::rgb(255, 222, 173),
-            Recognized::navy => Rgba::rgb(0, 0, 128),
-            Recognized::oldlace => Rgba::rgb(253, 245, 230),
-            Recognized::olive => Rgba::rgb(128, 128, 0),
-            Recognized::olivedrab => Rgba::rgb(107, 142, 35),
-            Recognized::orange => Rgba::rgb(255, 165, 0),
-            Recognized::orangered => Rgba::rgb(255, 69, 0),
-            Recognized::orchid => Rgba::rgb(218, 112, 214),
-            Recognized::palegoldenrod => Rgba::rgb(238, 232, 170),
-            Recognized::palegreen => Rgba::rgb(152, 251, 152),
-            Recognized::paleturquoise => Rgba::rgb(175, 238, 238),
-            Recognized::palevioletred => Rgba::rgb(219, 112, 147),
-            Recognized::papayawhip => Rgba::rgb(255, 239, 213),
-            Recognized::peachpuff => Rgba::rgb(255, 218, 185),
-            Recognized::peru => Rgba::rgb(205, 133, 63),
-            Recognized::pink => Rgba::rgb(255, 192, 203),
-            Recognized::plum => Rgba::rgb(221, 160, 221),
-            Recognized::powderblue => Rgba::rgb(176, 224, 230),
-            Recognized::purple => Rgba::rgb(128, 0, 128),
-            Recognized::red => Rgba::rgb(255, 0, 0),
-            Recognized::rosybrown => Rgba::rgb(188, 143, 143),
-            Recognized::royalblue => Rgba::rgb(65, 105, 225),
-            Recognized::saddlebrown => Rgba::rgb(139, 69, 19),
-            Recognized::salmon => Rgba::rgb(250, 128, 114),
-            Recognized::sandybrown => Rgba::rgb(244, 164, 96),
-            Recognized::seagreen => Rgba::rgb(46, 139, 87),
-            Recognized::seashell => Rgba::rgb(255, 245, 238),
-            Recognized::sienna => Rgba::rgb(160, 82, 45),
-            Recognized::silver => Rgba::rgb(192, 192, 192),
-            Recognized::skyblue => Rgba::rgb(135, 206, 235),
-            Recognized::slateblue => Rgba::rgb(106, 90, 205),
-            Recognized::slategray => Rgba::rgb(112, 128, 144),
-            Recognized::slategrey => Rgba::rgb(112, 128, 144),
-            Recognized::snow => Rgba::rgb(255, 250, 250),
-            Recognized::springgreen => Rgba::rgb(0, 255, 127),
-            Recognized::steelblue => Rgba::rgb(70, 130, 180),
-            Recognized::tan => Rgba::rgb(210, 180, 140),
-            Recognized::teal => Rgba::rgb(0, 128, 128),
-            Recognized::thistle => Rgba::rgb(216, 191, 216),
-            Recognized::tomato => Rgba::rgb(255, 99, 71),
-            Recognized::turquoise => Rgba::rgb(64, 224, 208),
-            Recognized::violet => Rgba::rgb(238, 130, 238),
-            Recognized::wheat => Rgba::rgb(245, 222, 179),
-            Recognized::white => Rgba::rgb(255, 255, 255),
-            Recognized::whitesmoke => Rgba::rgb(245, 245, 245),
-            Recognized::yellow => Rgba::rgb(255, 255, 0),
-            Recognized::yellowgreen => Rgba::rgb(154, 205, 50),
+        impl From<Recognized> for Rgba {
+            fn from(value: Recognized) -> Self {
+                match value {
+                    $(Recognized::$name => Rgba::rgb($r, $g, $b),)*
+                }
+            }
         }
-    }
+    };
+}
+
+recognized! {
+    aliceblue => (240, 248, 255),
+    antiquewhite => (250, 235, 215),
+    aqua => (0, 255, 255),
+    aquamarine => (127, 255, 212),
+    azure => (240, 255, 255),
+    beige => (245, 245, 220),
+    bisque => (255, 228, 196),
+    black => (0, 0, 0),
+    blanchedalmond => (255, 235, 205),
+    blue => (0, 0, 255),
+    blueviolet => (138, 43, 226),
+    brown => (165, 42, 42),
+    burlywood => (222, 184, 135),
+    cadetblue => (95, 158, 160),
+    chartreuse => (127, 255, 0),
+    chocolate => (210, 105, 30),
+    coral => (255, 127, 80),
+    cornflowerblue => (100, 149, 237),
+    cornsilk => (255, 248, 220),
+    crimson => (220, 20, 60),
+    cyan => (0, 255, 255),
+    darkblue => (0, 0, 139),
+    darkcyan => (0, 139, 139),
+    darkgoldenrod => (184, 134, 11),
+    darkgray => (169, 169, 169),
+    darkgreen => (0, 100, 0),
+    darkgrey => (169, 169, 169),
+    darkkhaki => (189, 183, 107),
+    darkmagenta => (139, 0, 139),
+    darkolivegreen => (85, 107, 47),
+    darkorange => (255, 140, 0),
+    darkorchid => (153, 50, 204),
+    darkred => (139, 0, 0),
+    darksalmon => (233, 150, 122),
+    darkseagreen => (143, 188, 143),
+    darkslateblue => (72, 61, 139),
+    darkslategray => (47, 79, 79),
+    darkslategrey => (47, 79, 79),
+    darkturquoise => (0, 206, 209),
+    darkviolet => (148, 0, 211),
+    deeppink => (255, 20, 147),
+    deepskyblue => (0, 191, 255),
+    dimgray => (105, 105, 105),
+    dimgrey => (105, 105, 105),
+    dodgerblue => (30, 144, 255),
+    firebrick => (178, 34, 34),
+    floralwhite => (255, 250, 240),
+    forestgreen => (34, 139, 34),
+    fuchsia => (255, 0, 255),
+    gainsboro => (220, 220, 220),
+    ghostwhite => (248, 248, 255),
+    gold => (255, 215, 0),
+    goldenrod => (218, 165, 32),
+    gray => (128, 128, 128),
+    grey => (128, 128, 128),
+    green => (0, 128, 0),
+    greenyellow => (173, 255, 47),
+    honeydew => (240, 255, 240),
+    hotpink => (255, 105, 180),
+    indianred => (205, 92, 92),
+    indigo => (75, 0, 130),
+    ivory => (255, 255, 240),
+    khaki => (240, 230, 140),
+    lavender => (230, 230, 250),
+    lavenderblush => (255, 240, 245),
+    lawngreen => (124, 252, 0),
+    lemonchiffon => (255, 250, 205),
+    lightblue => (173, 216, 230),
+    lightcoral => (240, 128, 128),
+    lightcyan => (224, 255, 255),
+    lightgoldenrodyellow => (250, 250, 210),
+    lightgray => (211, 211, 211),
+    lightgreen => (144, 238, 144),
+    lightgrey => (211, 211, 211),
+    lightpink => (255, 182, 193),
+    lightsalmon => (255, 160, 122),
+    lightseagreen => (32, 178, 170),
+    lightskyblue => (135, 206, 250),
+    lightslategray => (119, 136, 153),
+    lightslategrey => (119, 136, 153),
+    lightsteelblue => (176, 196, 222),
+    lightyellow => (255, 255, 224),
+    lime => (0, 255, 0),
+    limegreen => (50, 205, 50),
+    linen => (250, 240, 230),
+    magenta => (255, 0, 255),
+    maroon => (128, 0, 0),
+    mediumaquamarine => (102, 205, 170),
+    mediumblue => (0, 0, 205),
+    mediumorchid => (186, 85, 211),
+    mediumpurple => (147, 112, 219),
+    mediumseagreen => (60, 179, 113),
+    mediumslateblue => (123, 104, 238),
+    mediumspringgreen => (0, 250, 154),
+    mediumturquoise => (72, 209, 204),
+    mediumvioletred => (199, 21, 133),
+    midnightblue => (25, 25, 112),
+    mintcream => (245, 255, 250),
+    mistyrose => (255, 228, 225),
+    moccasin => (255, 228, 181),
+    navajowhite => (255, 222, 173),
+    navy => (0, 0, 128),
+    oldlace => (253, 245, 230),
+    olive => (128, 128, 0),
+    olivedrab => (107, 142, 35),
+    orange => (255, 165, 0),
+    orangered => (255, 69, 0),
+    orchid => (218, 112, 214),
+    palegoldenrod => (238, 232, 170),
+    palegreen => (152, 251, 152),
+    paleturquoise => (175, 238, 238),
+    palevioletred => (219, 112, 147),
+    papayawhip => (255, 239, 213),
+    peachpuff => (255, 218, 185),
+    peru => (205, 133, 63),
+    pink => (255, 192, 203),
+    plum => (221, 160, 221),
+    powderblue => (176, 224, 230),
+    purple => (128, 0, 128),
+    red => (255, 0, 0),
+    rosybrown => (188, 143, 143),
+    royalblue => (65, 105, 225),
+    saddlebrown => (139, 69, 19),
+    salmon => (250, 128, 114),
+    sandybrown => (244, 164, 96),
+    seagreen => (46, 139, 87),
+    seashell => (255, 245, 238),
+    sienna => (160, 82, 45),
+    silver => (192, 192, 192),
+    skyblue => (135, 206, 235),
+    slateblue => (106, 90, 205),
+    slategray => (112, 128, 144),
+    slategrey => (112, 128, 144),
+    snow => (255, 250, 250),
+    springgreen => (0, 255, 127),
+    steelblue => (70, 130, 180),
+    tan => (210, 180, 140),
+    teal => (0, 128, 128),
+    thistle => (216, 191, 216),
+    tomato => (255, 99, 71),
+    turquoise => (64, 224, 208),
+    violet => (238, 130, 238),
+    wheat => (245, 222, 179),
+    white => (255, 255, 255),
+    whitesmoke => (245, 245, 245),
+    yellow => (255, 255, 0),
+    yellowgreen => (154, 205, 50),
 }
 
 #[cfg(test)]
 mod tests {
     use crate::primitives::Recognized;
 
-    use super::Rgba;
+    use super::{Rgba, RECOGNIZED};
 
     #[test]
     fn color_parse() {
@@ -691,6 +641,17 @@ mod tests {
 
         assert_eq!("#ff0".parse::<Rgba>().unwrap(), Rgba::rgb(255, 255, 0));
 
+        assert_eq!(
+            "#ff000080".parse::<Rgba>().unwrap(),
+            Rgba::new(255, 0, 0, 128)
+        );
+
+        assert_eq!("#f00f".parse::<Rgba>().unwrap(), Rgba::rgb(255, 0, 0));
+
+        assert_eq!("#f008".parse::<Rgba>().unwrap(), Rgba::new(255, 0, 0, 0x88));
+
+        "#ff00f".parse::<Rgba>().expect_err("invalid hex length");
+
         assert_eq!(
             "rgb(128,102,100)".parse::<Rgba>().unwrap(),
             Rgba::rgb(128, 102, 100)
@@ -716,4 +677,169 @@ mod tests {
             Recognized::mintcream.into()
         );
     }
+
+    #[test]
+    fn rgba_parse() {
+        assert_eq!(
+            "rgba(0,0,0,0.5)".parse::<Rgba>().unwrap(),
+            Rgba::newf(0.0, 0.0, 0.0, 0.5)
+        );
+
+        assert_eq!(
+            "RGBA( 255, 0, 255 , 1 )".parse::<Rgba>().unwrap(),
+            Rgba::rgb(255, 0, 255)
+        );
+
+        "rgba(0,0,0,1.5)"
+            .parse::<Rgba>()
+            .expect_err("alpha out of range");
+
+        assert_eq!(
+            "rgba(0.5,0.0,1.0,1.0)".parse::<Rgba>().unwrap(),
+            Rgba::newf(0.5, 0.0, 1.0, 1.0)
+        );
+
+        "rgba(0.5,2.0,1.0,1.0)"
+            .parse::<Rgba>()
+            .expect_err("normalized value out of range");
+
+        assert_eq!(
+            "rgba(1,0.5,0,1)".parse::<Rgba>().unwrap(),
+            Rgba::newf(1.0 / 255.0, 0.5, 0.0, 1.0)
+        );
+
+        assert_eq!(
+            "rgba(255,0.5,0,1)".parse::<Rgba>().unwrap(),
+            Rgba::newf(1.0, 0.5, 0.0, 1.0)
+        );
+
+        "rgba(256,0.5,0,1)"
+            .parse::<Rgba>()
+            .expect_err("integer value out of range");
+
+        let color = Rgba::newf(1e-7, 0.5, 1.0, 1.0);
+
+        assert_eq!(color.to_string().parse::<Rgba>().unwrap(), color);
+    }
+
+    #[test]
+    fn recognized() {
+        assert_eq!(
+            Rgba::rgb(255, 0, 0).exact_recognized(),
+            Some(Recognized::red)
+        );
+
+        assert_eq!(Rgba::rgb(255, 0, 1).exact_recognized(), None);
+
+        assert_eq!(Rgba::new(255, 0, 0, 128).exact_recognized(), None);
+
+        assert_eq!(Rgba::rgb(3, 2, 4).nearest_recognized(), Recognized::black);
+
+        assert_eq!(Rgba::rgb(250, 2, 1).nearest_recognized(), Recognized::red);
+
+        for &recognized in RECOGNIZED {
+            let rgba: Rgba = recognized.into();
+
+            assert_eq!(
+                Rgba::from(rgba.nearest_recognized()),
+                rgba,
+                "{:?}",
+                recognized
+            );
+        }
+    }
+
+    #[test]
+    fn lerp() {
+        let black = Rgba::rgb(0, 0, 0);
+        let white = Rgba::rgb(255, 255, 255);
+
+        assert_eq!(black.lerp(white, 0.5), Rgba::rgbf(0.5, 0.5, 0.5));
+        assert_eq!(black.lerp(white, -1.0), black);
+        assert_eq!(black.lerp(white, 2.0), white);
+
+        let transparent_white = Rgba::new(255, 255, 255, 0);
+        let red = Rgba::rgb(255, 0, 0);
+
+        // straight alpha interpolation bleeds the transparent white into red.
+        assert_eq!(
+            transparent_white.lerp(red, 0.5),
+            Rgba::newf(1.0, 0.5, 0.5, 0.5)
+        );
+
+        assert_eq!(
+            transparent_white.lerp_premultiplied(red, 0.5),
+            Rgba::newf(1.0, 0.0, 0.0, 0.5)
+        );
+
+        assert_eq!(
+            transparent_white.lerp_premultiplied(transparent_white, 0.5),
+            Rgba::newf(0.0, 0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let colors = [
+            Rgba::rgb(255, 0, 255),
+            Rgba::rgb(1, 0, 1),
+            Rgba::rgb(0, 0, 0),
+            Rgba::new(12, 34, 56, 78),
+            Rgba::newf(0.5, 0.0, 1.0, 1.0),
+            Rgba::newf(1e-7, 0.333, 0.999, 0.0),
+            Recognized::mintcream.into(),
+        ];
+
+        for color in colors {
+            assert_eq!(color.to_string().parse::<Rgba>().unwrap(), color);
+        }
+
+        for i in 0..=255u8 {
+            let color = Rgba::new(i, 255 - i, i / 2, i);
+
+            assert_eq!(color.to_string().parse::<Rgba>().unwrap(), color);
+        }
+    }
+
+    #[test]
+    fn hsl_parse() {
+        assert_eq!(
+            "hsl(120,100%,50%)".parse::<Rgba>().unwrap(),
+            Rgba::rgb(0, 255, 0)
+        );
+
+        assert_eq!(
+            "hsl(0, 100%, 50%)".parse::<Rgba>().unwrap(),
+            Rgba::rgb(255, 0, 0)
+        );
+
+        assert_eq!(
+            "hsla(240,100%,50%,0.5)".parse::<Rgba>().unwrap(),
+            Rgba::newf(0.0, 0.0, 1.0, 0.5)
+        );
+
+        assert_eq!(
+            "hsl(0,0%,100%)".parse::<Rgba>().unwrap(),
+            Rgba::rgb(255, 255, 255)
+        );
+
+        "hsl(0,120%,50%)"
+            .parse::<Rgba>()
+            .expect_err("saturation out of range");
+    }
+
+    #[test]
+    fn channels() {
+        let red = Rgba::rgb(255, 0, 0);
+
+        assert_eq!(red.red(), 1.0);
+        assert_eq!(red.green(), 0.0);
+        assert_eq!(red.blue(), 0.0);
+        assert_eq!(red.alpha(), 1.0);
+
+        let red = red.with_alpha(0.5);
+
+        assert_eq!(red.alpha(), 0.5);
+        assert_eq!(red, Rgba::newf(1.0, 0.0, 0.0, 0.5));
+    }
 }
@@ -10,7 +10,7 @@ use nom::{
 
 use crate::Error;
 
-use super::Unit;
+use super::{Unit, ViewBox};
 
 /// A length is a distance measurement, given as a number along with a unit.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
@@ -49,6 +49,57 @@ impl FromStr for Length {
     }
 }
 
+/// The reference axis used to resolve percentage lengths.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Axis {
+    /// Resolve against the width, e.g. `x`, `width`, `cx`.
+    Horizontal,
+    /// Resolve against the height, e.g. `y`, `height`, `cy`.
+    Vertical,
+    /// Resolve against the normalized diagonal, e.g. `r`, `stroke-width`.
+    Diagonal,
+}
+
+impl Axis {
+    /// Returns the reference length of this axis in a `width` x `height` box.
+    ///
+    /// For [`Axis::Diagonal`] the reference length is `sqrt(width² + height²) / sqrt(2)`,
+    /// see [`https://www.w3.org/TR/SVG11/coords.html#Units`].
+    pub fn reference(&self, width: f32, height: f32) -> f32 {
+        match self {
+            Axis::Horizontal => width,
+            Axis::Vertical => height,
+            Axis::Diagonal => (width * width + height * height).sqrt() / std::f32::consts::SQRT_2,
+        }
+    }
+}
+
+/// The environment used to convert lengths to device pixels.
+///
+/// `px` and unitless lengths are device pixels, they are never scaled by `dpi`, which only applies to
+/// the physical units `in`, `cm`, `mm`, `pt` and `pc`. So the css ratio `1in = 96px` only holds at `96dpi`,
+/// to render css pixels on a high density display, scale the `px` results by `dpi / 96` instead.
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct LengthContext {
+    /// Device pixels per inch.
+    pub dpi: f32,
+    /// The font size in device pixels, used by `em` and `ex` units.
+    pub font_size: f32,
+    /// The viewport `(width, height)` in device pixels, used by percentages.
+    pub viewport: (f32, f32),
+}
+
+/// The css reference context: `96dpi`, `16px` font size and an empty viewport.
+impl Default for LengthContext {
+    fn default() -> Self {
+        Self {
+            dpi: 96.0,
+            font_size: 16.0,
+            viewport: (0.0, 0.0),
+        }
+    }
+}
+
 impl From<f32> for Length {
     fn from(value: f32) -> Self {
         Self(value, None)
@@ -96,11 +147,52 @@ impl Length {
     pub fn percentage(value: f32) -> Self {
         Self(value, Some(Unit::Percentages))
     }
+
+    /// Resolve a percentage length against `reference`.
+    ///
+    /// A percentage returns `reference * value / 100`, other lengths return the raw value unchanged,
+    /// their units are **not** converted, use [`to_px`](Self::to_px) for that.
+    pub fn resolve(&self, reference: f32) -> f32 {
+        match self.1 {
+            Some(Unit::Percentages) => reference * self.0 / 100.0,
+            _ => self.0,
+        }
+    }
+
+    /// Resolve a percentage length against the `viewbox` dimension of `axis`, see [`Axis::reference`].
+    ///
+    /// Like [`resolve`](Self::resolve), other lengths return the raw value unchanged.
+    pub fn resolve_in_box(&self, axis: Axis, viewbox: &ViewBox) -> f32 {
+        self.resolve(axis.reference(viewbox.width.0, viewbox.height.0))
+    }
+
+    /// Convert this length to device pixels.
+    ///
+    /// Physical units use `ctx.dpi` (see [`Unit::to_px_factor`]), `em` is `ctx.font_size`
+    /// and `ex` is half of `ctx.font_size`, percentages refer to the `ctx.viewport` dimension of `axis`.
+    /// `px` and unitless lengths are already device pixels and are returned unchanged, see [`LengthContext`].
+    pub fn to_px(&self, ctx: &LengthContext, axis: Axis) -> f32 {
+        let value = self.0;
+
+        let Some(unit) = self.1 else {
+            return value;
+        };
+
+        if let Some(factor) = unit.to_px_factor(ctx.dpi) {
+            return value * factor;
+        }
+
+        match unit {
+            Unit::Em => value * ctx.font_size,
+            Unit::Ex => value * ctx.font_size / 2.0,
+            _ => self.resolve(axis.reference(ctx.viewport.0, ctx.viewport.1)),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::primitives::Length;
+    use crate::primitives::{Axis, Length, LengthContext, ViewBox};
 
     #[test]
     fn display() {
@@ -110,4 +202,68 @@ mod tests {
 
         assert_eq!("99.1%".parse::<Length>().unwrap(), Length::percentage(99.1));
     }
+
+    #[test]
+    fn resolve() {
+        assert_eq!(Length::percentage(50.0).resolve(200.0), 100.0);
+        assert_eq!(Length::px(30.0).resolve(200.0), 30.0);
+        assert_eq!(Length(30.0, None).resolve(200.0), 30.0);
+
+        let viewbox = ViewBox {
+            x: 0.0.into(),
+            y: 0.0.into(),
+            width: 300.0.into(),
+            height: 400.0.into(),
+            aspect: None,
+        };
+
+        assert_eq!(
+            Length::percentage(10.0).resolve_in_box(Axis::Horizontal, &viewbox),
+            30.0
+        );
+
+        assert_eq!(
+            Length::percentage(10.0).resolve_in_box(Axis::Vertical, &viewbox),
+            40.0
+        );
+
+        let diagonal = Length::percentage(100.0).resolve_in_box(Axis::Diagonal, &viewbox);
+
+        assert!((diagonal - 500.0 / 2f32.sqrt()).abs() < 1e-4);
+
+        assert_eq!(
+            Length::px(10.0).resolve_in_box(Axis::Diagonal, &viewbox),
+            10.0
+        );
+    }
+
+    #[test]
+    fn to_px() {
+        let ctx = LengthContext {
+            dpi: 96.0,
+            font_size: 10.0,
+            viewport: (200.0, 100.0),
+        };
+
+        assert_eq!(Length::inch(1.0).to_px(&ctx, Axis::Horizontal), 96.0);
+        assert_eq!(Length::pt(72.0).to_px(&ctx, Axis::Horizontal), 96.0);
+        assert_eq!(Length::pc(6.0).to_px(&ctx, Axis::Horizontal), 96.0);
+        assert!((Length::cm(2.54).to_px(&ctx, Axis::Horizontal) - 96.0).abs() < 1e-4);
+        assert!((Length::mm(25.4).to_px(&ctx, Axis::Horizontal) - 96.0).abs() < 1e-4);
+        assert_eq!(Length::em(2.0).to_px(&ctx, Axis::Horizontal), 20.0);
+        assert_eq!(Length::ex(2.0).to_px(&ctx, Axis::Horizontal), 10.0);
+        assert_eq!(Length(7.0, None).to_px(&ctx, Axis::Horizontal), 7.0);
+
+        assert_eq!(
+            Length::percentage(50.0).to_px(&ctx, Axis::Horizontal),
+            100.0
+        );
+        assert_eq!(Length::percentage(50.0).to_px(&ctx, Axis::Vertical), 50.0);
+
+        // `dpi` only scales physical units, `px` are device pixels.
+        let ctx = LengthContext { dpi: 192.0, ..ctx };
+
+        assert_eq!(Length::inch(1.0).to_px(&ctx, Axis::Horizontal), 192.0);
+        assert_eq!(Length::px(96.0).to_px(&ctx, Axis::Horizontal), 96.0);
+    }
 }
@@ -1,6 +1,11 @@
-use std::fmt::Display;
+use std::{
+    fmt::Display,
+    ops::{Add, Sub},
+};
 
-use super::Unit;
+use crate::Result;
+
+use super::{Length, Unit};
 
 /// A 2d coordinate point.
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
@@ -109,3 +114,128 @@ impl Point {
         }
     }
 }
+
+/// Point arithmetic.
+///
+/// Only points with the same unit can be combined, no conversion is performed.
+/// `None` is the default unit `px`, so it is compatible with `Some(Unit::Px)`.
+impl Point {
+    fn common_unit(&self, other: &Point) -> Result<Option<Unit>> {
+        super::common_unit(self.unit, other.unit)
+    }
+
+    /// Returns `self + other`, or [`Error::UnitMismatch`](crate::Error::UnitMismatch) if the units are not the same.
+    pub fn checked_add(&self, other: &Point) -> Result<Point> {
+        Ok(Point {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            unit: self.common_unit(other)?,
+        })
+    }
+
+    /// Returns `self - other`, or [`Error::UnitMismatch`](crate::Error::UnitMismatch) if the units are not the same.
+    pub fn checked_sub(&self, other: &Point) -> Result<Point> {
+        Ok(Point {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            unit: self.common_unit(other)?,
+        })
+    }
+
+    /// Returns the euclidean distance between two points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the units are not the same.
+    pub fn distance(&self, other: &Point) -> Length {
+        let delta = *self - *other;
+
+        Length(delta.x.hypot(delta.y), delta.unit)
+    }
+
+    /// Returns the midpoint of two points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the units are not the same.
+    pub fn midpoint(&self, other: &Point) -> Point {
+        let sum = *self + *other;
+
+        Point {
+            x: sum.x / 2.0,
+            y: sum.y / 2.0,
+            unit: sum.unit,
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if the units are not the same, see [`Point::checked_add`].
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        match self.checked_add(&rhs) {
+            Ok(point) => point,
+            Err(err) => panic!("Can't add point {} to {}: {}", rhs, self, err),
+        }
+    }
+}
+
+/// # Panics
+///
+/// Panics if the units are not the same, see [`Point::checked_sub`].
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        match self.checked_sub(&rhs) {
+            Ok(point) => point,
+            Err(err) => panic!("Can't subtract point {} from {}: {}", rhs, self, err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, Length, Point, Unit};
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(
+            Point::mm(3.0, 4.0) - Point::mm(1.0, 1.0),
+            Point::mm(2.0, 3.0)
+        );
+        assert_eq!(
+            Point::mm(3.0, 4.0) + Point::mm(1.0, 1.0),
+            Point::mm(4.0, 5.0)
+        );
+
+        assert_eq!(
+            Point::from((1.0, 1.0)) + Point::px(1.0, 1.0),
+            Point::px(2.0, 2.0)
+        );
+
+        assert_eq!(
+            Point::mm(0.0, 0.0).distance(&Point::mm(3.0, 4.0)),
+            Length(5.0, Some(Unit::Mm))
+        );
+
+        assert_eq!(
+            Point::mm(0.0, 0.0).midpoint(&Point::mm(3.0, 4.0)),
+            Point::mm(1.5, 2.0)
+        );
+
+        assert!(matches!(
+            Point::mm(3.0, 4.0).checked_sub(&Point::cm(1.0, 1.0)),
+            Err(Error::UnitMismatch(Some(Unit::Mm), Some(Unit::Cm)))
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mixed_units() {
+        _ = Point::mm(3.0, 4.0) - Point::pt(1.0, 1.0);
+    }
+}
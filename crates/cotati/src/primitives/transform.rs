@@ -1,5 +1,7 @@
 use std::{fmt::Display, str::FromStr};
 
+use super::{Angle, Point};
+
 /// A memory represents of svg element's `transform` attribute.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub enum Transform {
@@ -44,6 +46,174 @@ impl Display for Transform {
     }
 }
 
+/// Transform composition and application.
+///
+/// All transforms use the svg column-vector convention: a point `(x, y)` is mapped to
+///
+/// ```text
+/// | a c e |   | x |
+/// | b d f | * | y |
+/// | 0 0 1 |   | 1 |
+/// ```
+///
+/// so `x' = a*x + c*y + e` and `y' = b*x + d*y + f`.
+impl Transform {
+    /// Create an identity transform.
+    pub fn identity() -> Self {
+        Self::Matrix {
+            a: 1.0,
+            b: 0.0,
+            c: 0.0,
+            d: 1.0,
+            e: 0.0,
+            f: 0.0,
+        }
+    }
+
+    /// Create a `translate(tx, ty)` transform.
+    pub fn translate(tx: f32, ty: f32) -> Self {
+        Self::Translate { tx, ty }
+    }
+
+    /// Create a `scale(sx, sy)` transform.
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        Self::Scale { sx, sy }
+    }
+
+    /// Create a `rotate(angle)` transform about the origin,
+    /// a positive angle rotates the x axis towards the y axis.
+    pub fn rotate(angle: Angle) -> Self {
+        Self::Rotate {
+            angle: angle.to_degrees(),
+            cx: 0.0,
+            cy: 0.0,
+        }
+    }
+
+    /// Create a `skewX(angle)` transform.
+    pub fn skew_x(angle: Angle) -> Self {
+        Self::SkewX(angle.to_degrees())
+    }
+
+    /// Create a `skewY(angle)` transform.
+    pub fn skew_y(angle: Angle) -> Self {
+        Self::SkewY(angle.to_degrees())
+    }
+
+    /// Returns the `[a, b, c, d, e, f]` components of the equivalent `matrix(a,b,c,d,e,f)` transform.
+    pub fn to_matrix(&self) -> [f32; 6] {
+        match *self {
+            Transform::Translate { tx, ty } => [1.0, 0.0, 0.0, 1.0, tx, ty],
+            Transform::Matrix { a, b, c, d, e, f } => [a, b, c, d, e, f],
+            Transform::Scale { sx, sy } => [sx, 0.0, 0.0, sy, 0.0, 0.0],
+            Transform::Rotate { angle, cx, cy } => {
+                let (sin, cos) = angle.to_radians().sin_cos();
+
+                // translate(cx, cy) rotate(angle) translate(-cx, -cy)
+                [
+                    cos,
+                    sin,
+                    -sin,
+                    cos,
+                    cx - cos * cx + sin * cy,
+                    cy - sin * cx - cos * cy,
+                ]
+            }
+            Transform::SkewX(angle) => [1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0],
+            Transform::SkewY(angle) => [1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0],
+        }
+    }
+
+    /// Returns a transform that applies `self` first and then `other`.
+    ///
+    /// With column vectors this is the matrix product `other * self`, which is also
+    /// the svg attribute `transform="<other> <self>"`.
+    pub fn then(self, other: Transform) -> Transform {
+        let [a1, b1, c1, d1, e1, f1] = self.to_matrix();
+        let [a2, b2, c2, d2, e2, f2] = other.to_matrix();
+
+        Transform::Matrix {
+            a: a2 * a1 + c2 * b1,
+            b: b2 * a1 + d2 * b1,
+            c: a2 * c1 + c2 * d1,
+            d: b2 * c1 + d2 * d1,
+            e: a2 * e1 + c2 * f1 + e2,
+            f: b2 * e1 + d2 * f1 + f2,
+        }
+    }
+
+    /// Map point `p` through this transform, the unit of `p` is kept.
+    pub fn apply_point(&self, p: Point) -> Point {
+        let [a, b, c, d, e, f] = self.to_matrix();
+
+        Point {
+            x: a * p.x + c * p.y + e,
+            y: b * p.x + d * p.y + f,
+            unit: p.unit,
+        }
+    }
+}
+
+/// The components of an affine [`Transform`], see [`Transform::decompose`].
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct DecomposedTransform {
+    /// The translation `(tx, ty)`.
+    pub translate: (f32, f32),
+    /// The scale factors `(sx, sy)`, `sy` is negative if the transform contains a reflection.
+    pub scale: (f32, f32),
+    /// The rotation angle.
+    pub rotation: Angle,
+    /// The skew angle along the x axis.
+    pub skew_x: Angle,
+}
+
+impl DecomposedTransform {
+    /// Compose the components back into one transform,
+    /// equivalent to the svg attribute `transform="translate(..) rotate(..) skewX(..) scale(..)"`.
+    pub fn compose(&self) -> Transform {
+        Transform::scale(self.scale.0, self.scale.1)
+            .then(Transform::skew_x(self.skew_x))
+            .then(Transform::rotate(self.rotation))
+            .then(Transform::translate(self.translate.0, self.translate.1))
+    }
+}
+
+impl Transform {
+    /// Decompose this transform into translate/rotate/skew/scale components,
+    /// so that `decompose().compose()` maps points the same way as `self`.
+    ///
+    /// For a (near) singular matrix only the translation is kept, the result has unit scale and no rotation/skew.
+    /// The singular test is relative to the length of the matrix columns, so uniformly small scales are still decomposed.
+    pub fn decompose(&self) -> DecomposedTransform {
+        let [a, b, c, d, e, f] = self.to_matrix();
+
+        let sx = (a * a + b * b).sqrt();
+        let det = a * d - b * c;
+
+        // |det| = |col1| * |col2| * sin(angle between the columns).
+        if sx == 0.0 || det.abs() <= f32::EPSILON * sx * (c * c + d * d).sqrt() {
+            return DecomposedTransform {
+                translate: (e, f),
+                scale: (1.0, 1.0),
+                rotation: Angle::zero(),
+                skew_x: Angle::zero(),
+            };
+        }
+
+        // [a c; b d] = rotate * [sx m; 0 sy], and [sx m; 0 sy] = skewX * scale.
+        let rotation = b.atan2(a);
+        let m = (a * c + b * d) / sx;
+        let sy = det / sx;
+
+        DecomposedTransform {
+            translate: (e, f),
+            scale: (sx, sy),
+            rotation: Angle::deg(rotation.to_degrees()),
+            skew_x: Angle::deg((m / sy).atan().to_degrees()),
+        }
+    }
+}
+
 /// transform string parser.
 mod parser {
 
@@ -51,6 +221,7 @@ mod parser {
         branch::alt,
         bytes::complete::{tag, take_while},
         combinator::{map_res, opt},
+        sequence::{pair, preceded},
         IResult,
     };
 
@@ -69,6 +240,29 @@ mod parser {
         ))(input)
     }
 
+    /// Describe why `input` doesn't start with a valid transform function.
+    pub(super) fn describe_error(input: &str) -> String {
+        let name = input
+            .split(|c: char| !c.is_ascii_alphabetic())
+            .next()
+            .unwrap_or_default();
+
+        match name {
+            "matrix" | "translate" | "scale" | "rotate" | "skewX" | "skewY" => {
+                format!("invalid arguments for `{}`", name)
+            }
+            _ => format!("unknown transform function `{}`", name),
+        }
+    }
+
+    /// Parse the `comma-wsp` separator between two arguments or two transform functions.
+    pub(super) fn separator(input: &str) -> IResult<&str, &str> {
+        let (input, _) = wsp(input)?;
+        let (input, _) = opt(tag(","))(input)?;
+
+        wsp(input)
+    }
+
     fn wsp(input: &str) -> IResult<&str, &str> {
         take_while(|c: char| c.is_ascii_whitespace())(input)
     }
@@ -92,22 +286,17 @@ mod parser {
         let (input, _) = tag("(")(input)?;
         let (input, _) = wsp(input)?;
         let (input, dx) = number(input)?;
+        let (input, dy) = opt(preceded(separator, number))(input)?;
         let (input, _) = wsp(input)?;
-        let (input, comma) = opt(tag(","))(input)?;
-        let (input, _) = wsp(input)?;
-
-        if !comma.is_some() {
-            let (input, _) = tag(")")(input)?;
-            return Ok((input, Transform::Translate { tx: dx, ty: 0.0 }));
-        }
-
-        let (input, dy) = number(input)?;
-
-        let (input, _) = wsp(input)?;
-
         let (input, _) = tag(")")(input)?;
 
-        return Ok((input, Transform::Translate { tx: dx, ty: dy }));
+        return Ok((
+            input,
+            Transform::Translate {
+                tx: dx,
+                ty: dy.unwrap_or(0.0),
+            },
+        ));
     }
 
     fn parse_matrix(input: &str) -> IResult<&str, Transform> {
@@ -125,11 +314,7 @@ mod parser {
         let mut i = input;
 
         for _ in 0..5 {
-            let (input, _) = wsp(i)?;
-            let (input, _) = tag(",")(input)?;
-            let (input, _) = wsp(input)?;
-
-            let (input, tx) = number(input)?;
+            let (input, tx) = preceded(separator, number)(i)?;
 
             mat3x3.push(tx);
 
@@ -158,22 +343,17 @@ mod parser {
         let (input, _) = tag("(")(input)?;
         let (input, _) = wsp(input)?;
         let (input, dx) = number(input)?;
+        let (input, dy) = opt(preceded(separator, number))(input)?;
         let (input, _) = wsp(input)?;
-        let (input, comma) = opt(tag(","))(input)?;
-        let (input, _) = wsp(input)?;
-
-        if !comma.is_some() {
-            let (input, _) = tag(")")(input)?;
-            return Ok((input, Transform::Scale { sx: dx, sy: dx }));
-        }
-
-        let (input, dy) = number(input)?;
-
-        let (input, _) = wsp(input)?;
-
         let (input, _) = tag(")")(input)?;
 
-        return Ok((input, Transform::Scale { sx: dx, sy: dy }));
+        return Ok((
+            input,
+            Transform::Scale {
+                sx: dx,
+                sy: dy.unwrap_or(dx),
+            },
+        ));
     }
 
     fn parse_rotation(input: &str) -> IResult<&str, Transform> {
@@ -182,35 +362,15 @@ mod parser {
         let (input, _) = tag("(")(input)?;
         let (input, _) = wsp(input)?;
         let (input, angle) = number(input)?;
+        let (input, center) = opt(pair(
+            preceded(separator, number),
+            preceded(separator, number),
+        ))(input)?;
         let (input, _) = wsp(input)?;
-        let (input, comma) = opt(tag(","))(input)?;
-        let (input, _) = wsp(input)?;
-
-        if !comma.is_some() {
-            let (input, _) = tag(")")(input)?;
-            return Ok((
-                input,
-                Transform::Rotate {
-                    angle,
-                    cx: 0.0,
-                    cy: 0.0,
-                },
-            ));
-        }
-
-        let (input, cx) = number(input)?;
-
-        let (input, _) = wsp(input)?;
-
-        let (input, _) = tag(",")(input)?;
-        let (input, _) = wsp(input)?;
-
-        let (input, cy) = number(input)?;
-
-        let (input, _) = wsp(input)?;
-
         let (input, _) = tag(")")(input)?;
 
+        let (cx, cy) = center.unwrap_or((0.0, 0.0));
+
         return Ok((input, Transform::Rotate { angle, cx, cy }));
     }
 
@@ -239,19 +399,173 @@ mod parser {
     }
 }
 
+impl Transform {
+    /// Parse a svg transform list, e.g. `translate(10,20) rotate(45) scale(2)`.
+    ///
+    /// The functions are composed left-to-right like the svg `transform` attribute,
+    /// so the rightmost function is applied to points first. A single function keeps its variant,
+    /// a list is composed into one [`Transform::Matrix`] and an empty string is the identity.
+    /// [`FromStr`] is implemented by this fn.
+    pub fn parse(s: &str) -> crate::Result<Transform> {
+        let mut input = s.trim_start();
+
+        let mut transform: Option<Transform> = None;
+
+        while !input.is_empty() {
+            let (i, next) = parser::parse_transform(input).map_err(|_| {
+                crate::Error::TransformStr(format!(
+                    "{} at {}: {}",
+                    s,
+                    s.len() - input.len(),
+                    parser::describe_error(input)
+                ))
+            })?;
+
+            transform = Some(match transform {
+                Some(transform) => next.then(transform),
+                None => next,
+            });
+
+            (input, _) = parser::separator(i)
+                .map_err(|err| crate::Error::TransformStr(format!("{}: {}", s, err)))?;
+        }
+
+        Ok(transform.unwrap_or(Transform::identity()))
+    }
+}
+
 impl FromStr for Transform {
     type Err = crate::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (_, transform) = parser::parse_transform(s)
-            .map_err(|err| crate::Error::TransformStr(err.to_string()))?;
-
-        Ok(transform)
+        Transform::parse(s)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Transform;
+    use crate::{Angle, Point};
+
+    use super::{DecomposedTransform, Transform};
+
+    fn assert_point_eq(lhs: Point, rhs: Point) {
+        assert!(
+            (lhs.x - rhs.x).abs() < 1e-5 && (lhs.y - rhs.y).abs() < 1e-5,
+            "{} != {}",
+            lhs,
+            rhs
+        );
+    }
+
+    #[test]
+    fn apply_point() {
+        assert_point_eq(
+            Transform::rotate(Angle::deg(90.0)).apply_point((1.0, 0.0).into()),
+            (0.0, 1.0).into(),
+        );
+
+        assert_point_eq(
+            Transform::translate(2.0, 3.0)
+                .then(Transform::scale(2.0, 2.0))
+                .apply_point((0.0, 0.0).into()),
+            (4.0, 6.0).into(),
+        );
+
+        assert_point_eq(
+            Transform::scale(2.0, 2.0)
+                .then(Transform::translate(2.0, 3.0))
+                .apply_point((0.0, 0.0).into()),
+            (2.0, 3.0).into(),
+        );
+
+        assert_point_eq(
+            Transform::Rotate {
+                angle: 90.0,
+                cx: 1.0,
+                cy: 1.0,
+            }
+            .apply_point((2.0, 1.0).into()),
+            (1.0, 2.0).into(),
+        );
+
+        assert_point_eq(
+            Transform::skew_x(Angle::deg(45.0)).apply_point((0.0, 1.0).into()),
+            (1.0, 1.0).into(),
+        );
+
+        assert_eq!(
+            Transform::identity().apply_point(Point::mm(1.0, 2.0)),
+            Point::mm(1.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn decompose() {
+        let assert_decompose = |expected: DecomposedTransform| {
+            let decomposed = expected.compose().decompose();
+
+            let values = |t: DecomposedTransform| {
+                let (Angle::deg(rotation), Angle::deg(skew_x)) = (t.rotation, t.skew_x) else {
+                    unreachable!()
+                };
+
+                [
+                    t.translate.0,
+                    t.translate.1,
+                    t.scale.0,
+                    t.scale.1,
+                    rotation,
+                    skew_x,
+                ]
+            };
+
+            for (lhs, rhs) in values(decomposed).iter().zip(values(expected)) {
+                assert!(
+                    (lhs - rhs).abs() < 1e-3,
+                    "{:?} != {:?}",
+                    decomposed,
+                    expected
+                );
+            }
+        };
+
+        assert_decompose(DecomposedTransform {
+            translate: (10.0, -4.0),
+            scale: (2.0, 3.0),
+            rotation: Angle::deg(30.0),
+            skew_x: Angle::deg(20.0),
+        });
+
+        assert_decompose(DecomposedTransform {
+            translate: (0.0, 0.0),
+            scale: (0.5, -1.5),
+            rotation: Angle::deg(-120.0),
+            skew_x: Angle::deg(0.0),
+        });
+
+        assert_eq!(
+            Transform::Matrix {
+                a: 1.0,
+                b: 2.0,
+                c: 2.0,
+                d: 4.0,
+                e: 5.0,
+                f: 6.0
+            }
+            .decompose(),
+            DecomposedTransform {
+                translate: (5.0, 6.0),
+                scale: (1.0, 1.0),
+                rotation: Angle::zero(),
+                skew_x: Angle::zero(),
+            }
+        );
+
+        let decomposed = Transform::scale(1e-4, 1e-4).decompose();
+
+        assert!((decomposed.scale.0 - 1e-4).abs() < 1e-9, "{:?}", decomposed);
+        assert!((decomposed.scale.1 - 1e-4).abs() < 1e-9, "{:?}", decomposed);
+        assert_eq!(decomposed.rotation, Angle::zero());
+    }
 
     #[test]
     fn matrix() {
@@ -357,4 +671,62 @@ mod tests {
             Transform::Translate { tx: 3.1, ty: 2.1 }
         );
     }
+
+    #[test]
+    fn parse_list() {
+        assert_point_eq(
+            Transform::parse("translate(10,20)")
+                .unwrap()
+                .apply_point((0.0, 0.0).into()),
+            (10.0, 20.0).into(),
+        );
+
+        let parsed = Transform::parse("translate(10,20) rotate(45), scale(2)").unwrap();
+
+        let composed = Transform::scale(2.0, 2.0)
+            .then(Transform::rotate(Angle::deg(45.0)))
+            .then(Transform::translate(10.0, 20.0));
+
+        for (lhs, rhs) in parsed.to_matrix().iter().zip(composed.to_matrix()) {
+            assert!((lhs - rhs).abs() < 1e-5, "{} != {}", parsed, composed);
+        }
+
+        assert_eq!(
+            Transform::parse("matrix(1 2 3 4 5 6)").unwrap(),
+            Transform::Matrix {
+                a: 1.0,
+                b: 2.0,
+                c: 3.0,
+                d: 4.0,
+                e: 5.0,
+                f: 6.0
+            }
+        );
+
+        assert_eq!(Transform::parse(" ").unwrap(), Transform::identity());
+
+        // `FromStr` parses the whole list too.
+        assert_eq!(
+            "translate(1) scale(2)".parse::<Transform>().unwrap(),
+            Transform::parse("translate(1) scale(2)").unwrap()
+        );
+
+        assert_eq!(
+            "rotate(30 1 2)".parse::<Transform>().unwrap(),
+            Transform::Rotate {
+                angle: 30.0,
+                cx: 1.0,
+                cy: 2.0
+            }
+        );
+
+        assert!(matches!(
+            Transform::parse("translate(1) skew(2)"),
+            Err(crate::Error::TransformStr(err)) if err.ends_with("unknown transform function `skew`")
+        ));
+
+        Transform::parse("translate(1) skew(2)").expect_err("unknown function");
+        Transform::parse("rotate(1,2)").expect_err("wrong number of arguments");
+        Transform::parse("scale(1").expect_err("unclosed");
+    }
 }
@@ -23,6 +23,35 @@ pub enum Unit {
     Percentages,
 }
 
+/// Returns the common unit of two lengths, or [`Error::UnitMismatch`](crate::Error::UnitMismatch) if they are not the same.
+///
+/// `None` is the default unit `px`, so it is compatible with `Some(Unit::Px)`.
+pub(crate) fn common_unit(lhs: Option<Unit>, rhs: Option<Unit>) -> crate::Result<Option<Unit>> {
+    match (lhs, rhs) {
+        (lhs, rhs) if lhs == rhs => Ok(lhs),
+        (None, Some(Unit::Px)) | (Some(Unit::Px), None) => Ok(Some(Unit::Px)),
+        (lhs, rhs) => Err(crate::Error::UnitMismatch(lhs, rhs)),
+    }
+}
+
+impl Unit {
+    /// Returns the multiplier converting a value in this unit to device pixels at `dpi`,
+    /// `1in = 2.54cm = 25.4mm = 72pt = 6pc`, `px` are device pixels and always return `1.0`.
+    ///
+    /// Returns `None` for the relative units `em`, `ex` and `%`, which need a font or viewport context.
+    pub fn to_px_factor(&self, dpi: f32) -> Option<f32> {
+        match self {
+            Unit::Px => Some(1.0),
+            Unit::In => Some(dpi),
+            Unit::Cm => Some(dpi / 2.54),
+            Unit::Mm => Some(dpi / 25.4),
+            Unit::Pt => Some(dpi / 72.0),
+            Unit::Pc => Some(dpi / 6.0),
+            Unit::Em | Unit::Ex | Unit::Percentages => None,
+        }
+    }
+}
+
 impl FromStr for Unit {
     type Err = crate::Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -62,3 +91,18 @@ impl AsRef<str> for Unit {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Unit;
+
+    #[test]
+    fn to_px_factor() {
+        assert_eq!(Unit::In.to_px_factor(96.0), Some(96.0));
+        assert_eq!(Unit::Px.to_px_factor(300.0), Some(1.0));
+        assert_eq!(Unit::Pt.to_px_factor(72.0), Some(1.0));
+        assert_eq!(Unit::Pc.to_px_factor(96.0), Some(16.0));
+        assert_eq!(Unit::Percentages.to_px_factor(96.0), None);
+        assert_eq!(Unit::Em.to_px_factor(96.0), None);
+    }
+}
@@ -41,6 +41,20 @@ pub enum MockDirection {
         x_rotation: Angle,
     },
 
+    Rect {
+        origin: Point,
+        width: Length,
+        height: Length,
+    },
+
+    RRect {
+        origin: Point,
+        width: Length,
+        height: Length,
+        rx: Length,
+        ry: Length,
+    },
+
     Canvas(Canvas),
 
     Label(String),
@@ -138,6 +152,30 @@ impl Renderer for MockRenderer {
         });
     }
 
+    fn rect(
+        &mut self,
+        origin: Point,
+        width: Length,
+        height: Length,
+        radii: Option<(Length, Length)>,
+    ) {
+        if let Some((rx, ry)) = radii {
+            self.0.push(MockDirection::RRect {
+                origin,
+                width,
+                height,
+                rx,
+                ry,
+            });
+        } else {
+            self.0.push(MockDirection::Rect {
+                origin,
+                width,
+                height,
+            });
+        }
+    }
+
     fn push_canvas(&mut self, canvas: Canvas) {
         self.0.push(MockDirection::Canvas(canvas));
     }
@@ -1,4 +1,36 @@
-use crate::{Angle, Canvas, Length, Point, Rgba, Transform};
+use crate::{common_unit, Angle, Canvas, Length, Point, Rgba, Transform};
+
+/// Resolve the corner radii of a `width` x `height` rectangle, following the svg `rect` rules.
+///
+/// If only one of `rx`/`ry` is provided the other one defaults to it, `rx` is clamped to half of `width`
+/// and `ry` is clamped to half of `height` (only when the radius and the dimension have the same unit).
+/// Returns `None` for square corners, i.e. if no radius is provided or one of them is zero.
+pub fn rect_radii(
+    width: Length,
+    height: Length,
+    rx: Option<Length>,
+    ry: Option<Length>,
+) -> Option<(Length, Length)> {
+    let (rx, ry) = match (rx, ry) {
+        (Some(rx), Some(ry)) => (rx, ry),
+        (Some(radius), None) | (None, Some(radius)) => (radius, radius),
+        (None, None) => return None,
+    };
+
+    if !(rx.0 > 0.0 && ry.0 > 0.0) {
+        return None;
+    }
+
+    let clamp = |radius: Length, dimension: Length| {
+        if common_unit(radius.1, dimension.1).is_ok() && radius.0 > dimension.0 / 2.0 {
+            Length(dimension.0 / 2.0, radius.1)
+        } else {
+            radius
+        }
+    };
+
+    Some((clamp(rx, width), clamp(ry, height)))
+}
 
 /// A rendering target must implement this trait.
 ///
@@ -63,6 +95,61 @@ pub trait Renderer {
         x_rotation: Angle,
     );
 
+    /// Draw a rectangle, the corners are rounded if `radii` is not `None`.
+    ///
+    /// The default implementation draws the outline with [`line`](Renderer::line) and
+    /// [`arc`](Renderer::arc) segments, the radii are resolved by [`rect_radii`]. No unit conversion is performed,
+    /// if the units of `origin`, `width`, `height` and non-zero `radii` are not the same, the rectangle is skipped with a warning.
+    fn rect(
+        &mut self,
+        origin: Point,
+        width: Length,
+        height: Length,
+        radii: Option<(Length, Length)>,
+    ) {
+        let radii = radii.and_then(|(rx, ry)| rect_radii(width, height, Some(rx), Some(ry)));
+
+        let unit = [width, height]
+            .into_iter()
+            .chain(radii.into_iter().flat_map(|(rx, ry)| [rx, ry]))
+            .try_fold(origin.unit, |unit, length| common_unit(unit, length.1));
+
+        let unit = match unit {
+            Ok(unit) => unit,
+            Err(err) => {
+                log::warn!("Renderer, skip rect: {}", err);
+                return;
+            }
+        };
+
+        let Point { x, y, .. } = origin;
+        let (w, h) = (width.0, height.0);
+        let (rx, ry) = radii.map(|(rx, ry)| (rx.0, ry.0)).unwrap_or((0.0, 0.0));
+
+        let point = |x: f32, y: f32| Point { x, y, unit };
+
+        let corner = |renderer: &mut Self, cx: f32, cy: f32, start_angle: f32| {
+            if radii.is_some() {
+                renderer.arc(
+                    Some(point(cx, cy)),
+                    (Length(rx, unit), Length(ry, unit)),
+                    Angle::deg(start_angle),
+                    Angle::deg(90.0),
+                    Angle::zero(),
+                );
+            }
+        };
+
+        self.line(Some(point(x + rx, y)), point(x + w - rx, y));
+        corner(self, x + w - rx, y + ry, 270.0);
+        self.line(None, point(x + w, y + h - ry));
+        corner(self, x + w - rx, y + h - ry, 0.0);
+        self.line(None, point(x + rx, y + h));
+        corner(self, x + rx, y + h - ry, 90.0);
+        self.line(None, point(x, y + ry));
+        corner(self, x + rx, y + ry, 180.0);
+    }
+
     /// Submits a series of graphics instructions for execution.
     fn submit(&mut self) -> Result<(), Self::Error>;
 }
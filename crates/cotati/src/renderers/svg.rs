@@ -1,38 +1,46 @@
 //! An implementation of [`Renderer`](super::Renderer) that supports svg image rendering target.
 
+use std::fmt::Write;
+
 use xml_dom::level2::{get_implementation, Document, Element, Node, RefNode};
 
-use crate::{Error, Result};
+use crate::{Error, Length, Point, Result, Rgba, Unit};
 
 use super::Renderer;
 
-#[allow(unused)]
 #[derive(Debug, PartialEq)]
 enum ScopeInstructon {
     Canvas(RefNode),
-    Path,
+    Path(RefNode),
     Transform(RefNode),
     Fill(RefNode),
     Stroke(RefNode),
+    /// A label doesn't create any element, the children are appended to the parent node.
+    Label(RefNode),
     Entity(String, RefNode),
 }
 
 struct RawSvgRenderer {
     document: RefNode,
     scoped_instructions: Vec<ScopeInstructon>,
+    /// The `<path>` element that collects segments drawn outside of a `path` scope.
+    implicit_path: Option<RefNode>,
+    /// The current point of path data, in user units.
+    current: Option<(f32, f32)>,
 }
 
-#[allow(unused)]
 impl RawSvgRenderer {
     fn new() -> Result<Self> {
         let implementation = get_implementation();
 
-        let document =
-            implementation.create_document(Some("http://www.w3.org/2000/svg"), None, None)?;
+        // the svg namespace is declared by the root `<svg>` element, see `push_canvas`.
+        let document = implementation.create_document(None, None, None)?;
 
         Ok(Self {
             document,
             scoped_instructions: Default::default(),
+            implicit_path: None,
+            current: None,
         })
     }
 
@@ -40,15 +48,73 @@ impl RawSvgRenderer {
         match self.scoped_instructions.last_mut() {
             Some(ScopeInstructon::Entity(_, node)) => node,
             Some(ScopeInstructon::Canvas(node)) => node,
+            Some(ScopeInstructon::Path(node)) => node,
             Some(ScopeInstructon::Transform(node)) => node,
             Some(ScopeInstructon::Fill(node)) => node,
             Some(ScopeInstructon::Stroke(node)) => node,
-            _ => &mut self.document,
+            Some(ScopeInstructon::Label(node)) => node,
+            None => &mut self.document,
         }
     }
 
-    fn is_in_path(&self) -> bool {
-        self.scoped_instructions.last() == Some(&ScopeInstructon::Path)
+    /// Create a new element and append it to the parent node, this ends the current path.
+    fn append_element(&mut self, name: &str) -> Result<RefNode> {
+        let element = self.document.create_element(name)?;
+
+        self.implicit_path = None;
+        self.current = None;
+
+        Ok(self.get_parent().append_child(element)?)
+    }
+
+    /// Append `data` to the path data of the current `path` scope, or of an implicit `<path>` element.
+    ///
+    /// If `from` is not the current point, a new subpath (or a new implicit `<path>` element) is started.
+    fn append_path_data(&mut self, from: Option<(f32, f32)>, data: &str) -> Result<()> {
+        let start = match (from, self.current) {
+            (Some(from), Some(current))
+                if (from.0 - current.0).abs() <= f32::EPSILON
+                    && (from.1 - current.1).abs() <= f32::EPSILON =>
+            {
+                None
+            }
+            (Some(from), _) => Some(from),
+            (None, Some(_)) => None,
+            (None, None) => Some((0.0, 0.0)),
+        };
+
+        let mut path = match (self.scoped_instructions.last(), &self.implicit_path) {
+            (Some(ScopeInstructon::Path(path)), _) => path.clone(),
+            (_, Some(path)) if start.is_none() => path.clone(),
+            _ => {
+                let mut path = self.append_element("path")?;
+                path.set_attribute("d", "")?;
+                self.implicit_path = Some(path.clone());
+                path
+            }
+        };
+
+        let mut d = path.get_attribute("d").unwrap_or_default();
+
+        if let Some((x, y)) = start {
+            if !d.is_empty() {
+                d.push(' ');
+            }
+
+            write!(d, "M{},{}", x, y).unwrap();
+        }
+
+        if !data.is_empty() {
+            if !d.is_empty() {
+                d.push(' ');
+            }
+
+            d.push_str(data);
+        }
+
+        path.set_attribute("d", &d)?;
+
+        Ok(())
     }
 
     fn pop(&mut self, n: usize) -> Result<()> {
@@ -60,25 +126,37 @@ impl RawSvgRenderer {
             self.scoped_instructions.pop();
         }
 
+        self.implicit_path = None;
+        self.current = None;
+
         Ok(())
     }
 
     fn push_entity(&mut self, id: &str) -> Result<()> {
-        let implementation = get_implementation();
+        let mut defs = self.append_element("defs")?;
 
-        let document =
-            implementation.create_document(Some("http://www.w3.org/2000/svg"), None, None)?;
+        let mut el = self.document.create_element("g")?;
+
+        el.set_attribute("id", id)?;
+
+        let el = defs.append_child(el)?;
 
         self.scoped_instructions
-            .push(ScopeInstructon::Entity(id.to_owned(), document));
+            .push(ScopeInstructon::Entity(id.to_owned(), el));
 
         Ok(())
     }
 
     fn push_canvas(&mut self, canvas: crate::Canvas) -> Result<()> {
-        let parent = self.get_parent();
+        let root = self.scoped_instructions.is_empty();
 
-        let mut el = parent.create_element("svg")?;
+        let mut el = self.append_element("svg")?;
+
+        if root {
+            el.set_attribute("xmlns", "http://www.w3.org/2000/svg")?;
+            el.set_attribute("xmlns:xlink", "http://www.w3.org/1999/xlink")?;
+            el.set_attribute("version", "1.1")?;
+        }
 
         el.set_attribute("width", canvas.width.to_string().as_str())?;
 
@@ -89,7 +167,7 @@ impl RawSvgRenderer {
                 "viewBox",
                 format!(
                     "{} {} {} {}",
-                    viewbox.x, viewbox.y, viewbox.width, viewbox.height
+                    viewbox.x.0, viewbox.y.0, viewbox.width.0, viewbox.height.0
                 )
                 .as_str(),
             )?;
@@ -105,14 +183,17 @@ impl RawSvgRenderer {
     }
 
     fn push_path(&mut self) -> Result<()> {
-        self.scoped_instructions.push(ScopeInstructon::Path);
+        let mut el = self.append_element("path")?;
+
+        el.set_attribute("d", "")?;
+
+        self.scoped_instructions.push(ScopeInstructon::Path(el));
+
         Ok(())
     }
 
     fn push_transform(&mut self, transform: crate::Transform) -> Result<()> {
-        let parent = self.get_parent();
-
-        let mut el = parent.create_element("g")?;
+        let mut el = self.append_element("g")?;
 
         el.set_attribute("transform", transform.to_string().as_str())?;
 
@@ -123,11 +204,15 @@ impl RawSvgRenderer {
     }
 
     fn push_fill(&mut self, color: crate::Rgba) -> Result<()> {
-        let parent = self.get_parent();
+        let mut el = self.append_element("g")?;
+
+        let (color, opacity) = paint(color);
 
-        let mut el = parent.create_element("g")?;
+        el.set_attribute("fill", color.as_str())?;
 
-        el.set_attribute("color", color.to_string().as_str())?;
+        if opacity < 1.0 {
+            el.set_attribute("fill-opacity", opacity.to_string().as_str())?;
+        }
 
         self.scoped_instructions.push(ScopeInstructon::Fill(el));
 
@@ -135,38 +220,58 @@ impl RawSvgRenderer {
     }
 
     fn push_stroke(&mut self, color: crate::Rgba, width: crate::Length) -> Result<()> {
-        let parent = self.get_parent();
+        let mut el = self.append_element("g")?;
 
-        let mut el = parent.create_element("g")?;
+        let (color, opacity) = paint(color);
 
-        el.set_attribute("stroke", color.to_string().as_str())?;
+        el.set_attribute("stroke", color.as_str())?;
         el.set_attribute("stroke-width", width.to_string().as_str())?;
 
+        if opacity < 1.0 {
+            el.set_attribute("stroke-opacity", opacity.to_string().as_str())?;
+        }
+
         self.scoped_instructions.push(ScopeInstructon::Stroke(el));
 
         Ok(())
     }
 
     fn push_label(&mut self, _label: &str) -> Result<()> {
+        let parent = self.get_parent().clone();
+
+        self.scoped_instructions
+            .push(ScopeInstructon::Label(parent));
+
         Ok(())
     }
 
     fn entity_ref(&mut self, id: &str) -> Result<()> {
-        let parent = self.get_parent();
-
-        let entity_ref = parent.create_entity_reference(id)?;
+        let mut el = self.append_element("use")?;
 
-        parent.append_child(entity_ref);
+        el.set_attribute("xlink:href", format!("#{}", id).as_str())?;
 
         Ok(())
     }
 
     fn move_to(&mut self, to: crate::Point) -> Result<()> {
-        todo!()
+        let to = user_point(to)?;
+
+        self.append_path_data(Some(to), "")?;
+
+        self.current = Some(to);
+
+        Ok(())
     }
 
     fn line(&mut self, from: Option<crate::Point>, to: crate::Point) -> Result<()> {
-        todo!()
+        let from = from.map(user_point).transpose()?;
+        let to = user_point(to)?;
+
+        self.append_path_data(from, &format!("L{},{}", to.0, to.1))?;
+
+        self.current = Some(to);
+
+        Ok(())
     }
 
     fn quadratic_bezier(
@@ -175,7 +280,14 @@ impl RawSvgRenderer {
         ctrl: crate::Point,
         to: crate::Point,
     ) -> Result<()> {
-        todo!()
+        let from = from.map(user_point).transpose()?;
+        let (ctrl, to) = (user_point(ctrl)?, user_point(to)?);
+
+        self.append_path_data(from, &format!("Q{},{} {},{}", ctrl.0, ctrl.1, to.0, to.1))?;
+
+        self.current = Some(to);
+
+        Ok(())
     }
 
     fn cubic_bezier(
@@ -185,7 +297,20 @@ impl RawSvgRenderer {
         ctrl2: crate::Point,
         to: crate::Point,
     ) -> Result<()> {
-        todo!()
+        let from = from.map(user_point).transpose()?;
+        let (ctrl1, ctrl2, to) = (user_point(ctrl1)?, user_point(ctrl2)?, user_point(to)?);
+
+        self.append_path_data(
+            from,
+            &format!(
+                "C{},{} {},{} {},{}",
+                ctrl1.0, ctrl1.1, ctrl2.0, ctrl2.1, to.0, to.1
+            ),
+        )?;
+
+        self.current = Some(to);
+
+        Ok(())
     }
 
     fn arc(
@@ -196,10 +321,126 @@ impl RawSvgRenderer {
         sweep_angle: crate::Angle,
         x_rotation: crate::Angle,
     ) -> Result<()> {
-        todo!()
+        let (rx, ry) = (user_length(raddii.0)?, user_length(raddii.1)?);
+        let rotation = x_rotation.to_radians();
+        let (start, sweep) = (start_angle.to_radians(), sweep_angle.to_radians());
+
+        let offset = |angle: f32| {
+            let (x, y) = (rx * angle.cos(), ry * angle.sin());
+
+            (
+                x * rotation.cos() - y * rotation.sin(),
+                x * rotation.sin() + y * rotation.cos(),
+            )
+        };
+
+        // without a center, the arc starts at the current point.
+        let center = match center {
+            Some(center) => user_point(center)?,
+            None => {
+                let current = self.current.unwrap_or((0.0, 0.0));
+                let (dx, dy) = offset(start);
+                (current.0 - dx, current.1 - dy)
+            }
+        };
+
+        let point = |angle: f32| {
+            let (dx, dy) = offset(angle);
+            (round(center.0 + dx), round(center.1 + dy))
+        };
+
+        // an arc command can't draw a closed ellipse, so it is split into two halves.
+        let steps = if sweep.abs() >= std::f32::consts::TAU {
+            vec![(start, sweep / 2.0), (start + sweep / 2.0, sweep / 2.0)]
+        } else {
+            vec![(start, sweep)]
+        };
+
+        let mut data = String::new();
+
+        let mut end = point(start);
+
+        for (from, sweep) in steps {
+            end = point(from + sweep);
+
+            if !data.is_empty() {
+                data.push(' ');
+            }
+
+            write!(
+                data,
+                "A{},{} {} {} {} {},{}",
+                rx,
+                ry,
+                round(x_rotation.to_degrees()),
+                (sweep.abs() > std::f32::consts::PI) as u8,
+                (sweep > 0.0) as u8,
+                end.0,
+                end.1
+            )
+            .unwrap();
+        }
+
+        self.append_path_data(Some(point(start)), &data)?;
+
+        self.current = Some(end);
+
+        Ok(())
+    }
+
+    fn rect(
+        &mut self,
+        origin: Point,
+        width: Length,
+        height: Length,
+        radii: Option<(Length, Length)>,
+    ) -> Result<()> {
+        let mut el = self.append_element("rect")?;
+
+        el.set_attribute("x", Length(origin.x, origin.unit).to_string().as_str())?;
+        el.set_attribute("y", Length(origin.y, origin.unit).to_string().as_str())?;
+        el.set_attribute("width", width.to_string().as_str())?;
+        el.set_attribute("height", height.to_string().as_str())?;
+
+        if let Some((rx, ry)) = radii {
+            el.set_attribute("rx", rx.to_string().as_str())?;
+            el.set_attribute("ry", ry.to_string().as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the factor converting `unit` into svg user units.
+///
+/// Path data has no units, so the absolute units are converted with the css ratio `1in = 96px`,
+/// the relative units `em`, `ex` and `%` return [`Error::PathUnit`].
+fn user_unit_factor(unit: Option<Unit>) -> Result<f32> {
+    match unit {
+        None => Ok(1.0),
+        Some(unit) => unit.to_px_factor(96.0).ok_or(Error::PathUnit(unit)),
     }
 }
 
+fn user_length(length: Length) -> Result<f32> {
+    Ok(length.0 * user_unit_factor(length.1)?)
+}
+
+fn user_point(point: Point) -> Result<(f32, f32)> {
+    let factor = user_unit_factor(point.unit)?;
+
+    Ok((point.x * factor, point.y * factor))
+}
+
+/// Rounds path data coordinates to 4 decimal places, to hide the trigonometric noise.
+fn round(v: f32) -> f32 {
+    (v * 1e4).round() / 1e4 + 0.0
+}
+
+/// A [`Renderer`] that builds a svg 1.1 document.
+///
+/// The coordinates of path segments are converted to user units, a relative unit
+/// (`em`, `ex` or `%`) in path data makes [`submit`](Renderer::submit) fail with [`Error::PathUnit`].
 pub struct SvgRenderer {
     raw: Option<RawSvgRenderer>,
     error: Option<Error>,
@@ -216,7 +457,7 @@ impl SvgRenderer {
         })
     }
 
-    /// Serialize svg into xml string.
+    /// Serialize svg into xml string, returns `None` if the document is not [`submit`](Renderer::submit)ted.
     pub fn to_string(self) -> Option<String> {
         self.svg
     }
@@ -376,6 +617,20 @@ impl Renderer for SvgRenderer {
         });
     }
 
+    fn rect(
+        &mut self,
+        origin: Point,
+        width: Length,
+        height: Length,
+        radii: Option<(Length, Length)>,
+    ) {
+        self.pcall(|mut raw| {
+            raw.rect(origin, width, height, radii)?;
+
+            Ok(raw)
+        });
+    }
+
     fn submit(&mut self) -> std::result::Result<(), Self::Error> {
         if let Some(error) = self.error.take() {
             return Err(error);
@@ -388,3 +643,110 @@ impl Renderer for SvgRenderer {
         Ok(())
     }
 }
+
+/// Returns the `#rrggbb` form of `color` and its opacity.
+fn paint(color: Rgba) -> (String, f32) {
+    let [r, g, b] = [color.0, color.1, color.2].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+
+    (format!("#{:02x}{:02x}{:02x}", r, g, b), color.3)
+}
+
+#[cfg(test)]
+mod tests {
+    use xml_dom::{
+        level2::{Node, RefNode},
+        parser::read_xml,
+    };
+
+    use crate::{
+        combinator::{
+            aspect, canvas, circle, entity, entity_ref, fill, label, rounded_rect, stroke, viewbox,
+        },
+        Drawing, Error, Length, MeetOrSlice, PreserveAspectRatio, Renderer,
+    };
+
+    use super::SvgRenderer;
+
+    fn count_elements(node: &RefNode) -> usize {
+        node.child_nodes()
+            .iter()
+            .map(|child| {
+                if child.node_type() == xml_dom::level2::NodeType::Element {
+                    1 + count_elements(child)
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_svg_renderer() {
+        let mut renderer = SvgRenderer::new().unwrap();
+
+        canvas(
+            aspect(
+                viewbox((10.0, 10.0), 0.0, 0.0, 10.0, 10.0),
+                PreserveAspectRatio::xMaxYMax(MeetOrSlice::Meet),
+            ),
+            fill(
+                (255, 0, 255),
+                stroke(
+                    (255, 0, 255),
+                    1.0,
+                    label(
+                        "content",
+                        (
+                            circle((20.0, 20.0), 10.0),
+                            circle((20.0, 20.0), Length::pc(1.0)),
+                            |renderer: &mut SvgRenderer| {
+                                renderer.line(Some((0.0, 0.0).into()), (5.0, 5.0).into());
+                                Ok(())
+                            },
+                            rounded_rect((0.0, 0.0), 4.0, 4.0, Some(1.0), None::<f32>),
+                            entity("dot", circle((1.0, 1.0), 1.0)),
+                            entity_ref("dot"),
+                        ),
+                    ),
+                ),
+            ),
+        )
+        .render(&mut renderer)
+        .unwrap();
+
+        renderer.submit().unwrap();
+
+        let svg = renderer.to_string().unwrap();
+
+        assert!(svg.contains(r#"viewBox="0 0 10 10""#), "{}", svg);
+        assert!(svg.contains(r#"preserveAspectRatio="xMaxYMax meet""#));
+        assert!(svg.contains(r##"fill="#ff00ff""##));
+        assert!(svg.contains(r#"d="M30,20 A10,10 0 0 1 10,20 A10,10 0 0 1 30,20""#));
+        // 1pc = 16 user units.
+        assert!(svg.contains(r#"d="M36,20 A16,16 0 0 1 4,20 A16,16 0 0 1 36,20""#));
+        assert!(svg.contains(r#"rx="1""#));
+
+        let document = read_xml(&svg).unwrap();
+
+        // svg, fill g, stroke g, 3 paths, rect, defs, entity g, entity path, use
+        assert_eq!(count_elements(&document), 11);
+    }
+
+    #[test]
+    fn test_svg_renderer_errors() {
+        let mut renderer = SvgRenderer::new().unwrap();
+
+        renderer.pop(1);
+
+        assert!(matches!(renderer.submit(), Err(Error::Pop(1))));
+
+        // relative units can't be converted to user units.
+        let mut renderer = SvgRenderer::new().unwrap();
+
+        circle((0.0, 0.0), Length::em(1.0))
+            .render(&mut renderer)
+            .unwrap();
+
+        assert!(matches!(renderer.submit(), Err(Error::PathUnit(_))));
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{ComponentType, Id};
+
+/// The error type used by this crate.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The snapshot was taken from a world with a different schema.
+    #[error("Schema mismatch, expect {expect:#x}, but got {got:#x}")]
+    SchemaMismatch { expect: u64, got: u64 },
+
+    /// The component type is not registered or not serializable in this world.
+    #[error("Unknown component type: {0}")]
+    UnknownComponentType(ComponentType),
+
+    /// The registered serializable rust type doesn't match the component value.
+    #[error("Component type mismatch: {0}")]
+    ComponentTypeMismatch(ComponentType),
+
+    /// The component id doesn't refer to an existing component.
+    #[error("Unknown component: {0}")]
+    UnknownComponent(Id),
+
+    /// Error returns by serde_json.
+    #[error(transparent)]
+    SerdeJsonError(#[from] serde_json::Error),
+}
+
+/// The result type used by this crate.
+pub type Result<T> = std::result::Result<T, Error>;
@@ -1,5 +1,9 @@
+#[cfg(feature = "serde")]
+mod errors;
 mod primitives;
 mod world;
+#[cfg(feature = "serde")]
+pub use errors::*;
 pub use primitives::*;
 pub use world::*;
 
@@ -19,6 +23,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_registered_types() {
+        struct A;
+        struct B;
+
+        ecs_system!(A, B);
+
+        let mut world = World::new(registered_types());
+
+        let entity = world.new_entity();
+
+        let (a, _) = world.new_component(A, [&entity]);
+        let (b, _) = world.new_component(B, [&entity]);
+
+        assert_eq!(
+            world.attached_component(&entity, A::component_type()),
+            Some(a)
+        );
+        assert_eq!(
+            world.attached_component(&entity, B::component_type()),
+            Some(b)
+        );
+    }
+
+    #[test]
+    fn test_world_schema_hash() {
+        static C1: ComponentType = ComponentType::new(1);
+        static C2: ComponentType = ComponentType::new(2);
+        static C3: ComponentType = ComponentType::new(3);
+
+        assert_eq!(
+            World::new(&[C1, C2]).schema_hash(),
+            World::new(&[C2, C1]).schema_hash()
+        );
+
+        assert_ne!(
+            World::new(&[C1, C2]).schema_hash(),
+            World::new(&[C1, C2, C3]).schema_hash()
+        );
+
+        assert_ne!(
+            World::new_with_schema(&[C1, C2], schema_hash(&["A", "B"])).schema_hash(),
+            World::new_with_schema(&[C1, C2], schema_hash(&["B", "A"])).schema_hash()
+        );
+    }
+
     #[test]
     fn test_world() {
         static C1: ComponentType = ComponentType::new(1);
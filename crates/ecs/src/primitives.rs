@@ -27,6 +27,7 @@ impl Display for Sequence {
 
 /// The type id of one component type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ComponentType(u16);
 
 impl ComponentType {
@@ -81,6 +82,7 @@ impl Default for ReferenceType {
 /// - `ComponentType`: [1~15)bits.
 /// - `Sequence`: (16~64)bits.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Id(u64);
 
 impl AsRef<Id> for Id {
@@ -92,13 +94,10 @@ impl AsRef<Id> for Id {
 impl Display for Id {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.reference_type() {
-            ReferenceType::Entity => write!(f, "entity({})", self.sequence().0),
-            ReferenceType::Component => write!(
-                f,
-                "component({},{})",
-                self.sequence().0,
-                self.component_type().0
-            ),
+            ReferenceType::Entity => write!(f, "E{}", self.sequence().0),
+            ReferenceType::Component => {
+                write!(f, "C{}:T{}", self.sequence().0, self.component_type().0)
+            }
         }
     }
 }
@@ -141,6 +140,68 @@ impl Id {
     pub fn reference_type(&self) -> ReferenceType {
         (self.0 as u8).into()
     }
+
+    /// Returns true if this is an entity id.
+    pub fn is_entity(&self) -> bool {
+        self.reference_type() == ReferenceType::Entity
+    }
+
+    /// Returns true if this is a component id.
+    pub fn is_component(&self) -> bool {
+        self.reference_type() == ReferenceType::Component
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+const fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        hash ^= bytes[idx] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        idx += 1;
+    }
+
+    hash
+}
+
+/// Calculate the schema hash of an ordered component name list, see [`World::schema_hash`](crate::World::schema_hash).
+///
+/// The hash changes if a name is added, removed, renamed or reordered.
+pub const fn schema_hash(names: &[&str]) -> u64 {
+    let mut hash = FNV_OFFSET_BASIS;
+    let mut idx = 0;
+
+    while idx < names.len() {
+        // the index separates the names, so `["ab", "c"]` and `["a", "bc"]` differ.
+        hash = fnv1a(hash, &(idx as u64).to_le_bytes());
+        hash = fnv1a(hash, names[idx].as_bytes());
+        idx += 1;
+    }
+
+    hash
+}
+
+/// Calculate the hash of a component type set, the order of `component_types` doesn't matter.
+pub(crate) fn component_types_hash<'a, I>(component_types: I) -> u64
+where
+    I: IntoIterator<Item = &'a ComponentType>,
+{
+    let mut component_types = component_types
+        .into_iter()
+        .map(|component_type| component_type.0)
+        .collect::<Vec<_>>();
+
+    component_types.sort();
+    component_types.dedup();
+
+    component_types
+        .into_iter()
+        .fold(FNV_OFFSET_BASIS, |hash, component_type| {
+            fnv1a(hash, &component_type.to_le_bytes())
+        })
 }
 
 /// A component rust type must implement this trait.
@@ -149,8 +210,20 @@ pub trait AsComponent {
     fn component_type() -> &'static ComponentType;
 }
 /// A macro to drive [`AsComponent`] trait for types.
+///
+/// The component types are assigned in the declaration order, the macro also generates:
+///
+/// - `pub const COMPONENT_TYPES: &[ComponentType]`, all declared component types in order.
+/// - `pub fn registered_types() -> &'static [ComponentType]`, returns `COMPONENT_TYPES`,
+///   which can be passed to [`World::new`](crate::World::new) directly.
+/// - `pub const SCHEMA_HASH: u64`, the [`schema_hash`] of the declared component names,
+///   which can be passed to [`World::new_with_schema`](crate::World::new_with_schema).
 #[macro_export]
 macro_rules! ecs_system {
+    (@unit $_n:tt) => {
+        ()
+    };
+
     (@step $_idx:expr,) => {};
 
     (@step $idx:expr, $head:tt, $($tail:tt,)*) => {
@@ -167,6 +240,32 @@ macro_rules! ecs_system {
 
     ($($n:tt),*) => {
         $crate::ecs_system!(@step 0usize, $($n,)*);
+
+        /// All component types declared by `ecs_system!`, in declaration order.
+        #[allow(unused)]
+        pub const COMPONENT_TYPES: &[$crate::ComponentType] = &{
+            const LEN: usize = [$($crate::ecs_system!(@unit $n)),*].len();
+
+            let mut types = [$crate::ComponentType::new(0); LEN];
+            let mut idx = 0;
+
+            while idx < LEN {
+                types[idx] = $crate::ComponentType::new(idx as u16);
+                idx += 1;
+            }
+
+            types
+        };
+
+        /// Returns all component types declared by `ecs_system!`.
+        #[allow(unused)]
+        pub fn registered_types() -> &'static [$crate::ComponentType] {
+            COMPONENT_TYPES
+        }
+
+        /// The schema hash of the component names declared by `ecs_system!`.
+        #[allow(unused)]
+        pub const SCHEMA_HASH: u64 = $crate::schema_hash(&[$(stringify!($n)),*]);
     }
 }
 
@@ -174,7 +273,7 @@ macro_rules! ecs_system {
 mod tests {
     use std::panic::catch_unwind;
 
-    use super::{ComponentType, Sequence};
+    use super::{schema_hash, ComponentType, Id, ReferenceType, Sequence};
 
     #[test]
     fn test_sequnce_out_of_range() {
@@ -188,6 +287,37 @@ mod tests {
         catch_unwind(|| ComponentType::new(2u16.pow(15))).expect_err("out of range");
     }
 
+    #[test]
+    fn test_id() {
+        let entity = Id::from((
+            Sequence::new(7),
+            ComponentType::new(0),
+            ReferenceType::Entity,
+        ));
+
+        assert!(entity.is_entity());
+        assert!(!entity.is_component());
+        assert_eq!(entity.to_string(), "E7");
+
+        let component = Id::from((
+            Sequence::new(42),
+            ComponentType::new(3),
+            ReferenceType::Component,
+        ));
+
+        assert!(component.is_component());
+        assert!(!component.is_entity());
+        assert_eq!(component.to_string(), "C42:T3");
+    }
+
+    #[test]
+    fn test_schema_hash() {
+        assert_eq!(schema_hash(&["A", "B"]), schema_hash(&["A", "B"]));
+        assert_ne!(schema_hash(&["A", "B"]), schema_hash(&["B", "A"]));
+        assert_ne!(schema_hash(&["A", "B"]), schema_hash(&["A", "B", "C"]));
+        assert_ne!(schema_hash(&["AB", "C"]), schema_hash(&["A", "BC"]));
+    }
+
     #[test]
     fn test_ecs_system_macro() {
         struct A;
@@ -201,5 +331,16 @@ mod tests {
         assert_eq!(*A::component_type(), ComponentType::new(0));
         assert_eq!(*B::component_type(), ComponentType::new(1));
         assert_eq!(*C::component_type(), ComponentType::new(2));
+
+        assert_eq!(
+            registered_types(),
+            &[
+                *A::component_type(),
+                *B::component_type(),
+                *C::component_type()
+            ]
+        );
+
+        assert_eq!(SCHEMA_HASH, schema_hash(&["A", "B", "C"]));
     }
 }
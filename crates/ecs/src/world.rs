@@ -4,7 +4,12 @@ use std::{
     u64,
 };
 
-use crate::{AsComponent, ComponentType, Id, ReferenceType, Sequence};
+use crate::{component_types_hash, AsComponent, ComponentType, Id, ReferenceType, Sequence};
+
+#[cfg(feature = "serde")]
+mod snapshot;
+#[cfg(feature = "serde")]
+pub use snapshot::*;
 
 /// A entity component container.
 #[derive(Default)]
@@ -121,6 +126,11 @@ pub struct World {
     entities: HashMap<Id, Entity>,
     /// component_type => component_id => component.
     component_types: HashMap<ComponentType, HashMap<Id, Component>>,
+    /// The schema hash of this world.
+    schema_hash: u64,
+    /// serializers of components that can be persisted in a [`WorldSnapshot`].
+    #[cfg(feature = "serde")]
+    serializers: HashMap<ComponentType, snapshot::ComponentSerializer>,
 }
 
 impl World {
@@ -151,6 +161,15 @@ impl World {
 impl World {
     /// Create a new `World` with supports component types.
     pub fn new<I>(component_types: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<ComponentType>,
+    {
+        Self::new_with_schema(component_types, 0)
+    }
+
+    /// Create a new `World` with supports component types and the `SCHEMA_HASH` generated by [`ecs_system!`](crate::ecs_system).
+    pub fn new_with_schema<I>(component_types: I, schema_hash: u64) -> Self
     where
         I: IntoIterator,
         I::Item: AsRef<ComponentType>,
@@ -162,8 +181,22 @@ impl World {
                 .insert(component_type.as_ref().clone(), Default::default());
         }
 
+        this.schema_hash =
+            component_types_hash(this.component_types.keys()) ^ schema_hash.rotate_left(1);
+
         this
     }
+
+    /// Returns the schema hash of this world.
+    ///
+    /// The hash covers the registered component types and the schema hash passed to [`new_with_schema`](Self::new_with_schema),
+    /// so it changes when a component type is added/removed or the [`ecs_system!`](crate::ecs_system) declarations are reordered.
+    ///
+    /// Component [`Id`]s encode the component type, so a persistence layer should store this hash
+    /// with the persisted ids and refuse to load them into a world with a different hash.
+    pub fn schema_hash(&self) -> u64 {
+        self.schema_hash
+    }
     /// Create a new entity in this world.
     pub fn new_entity(&mut self) -> Id {
         let id = (
@@ -199,6 +232,9 @@ impl World {
     /// Add a new component to entities.
     ///
     /// On success, returns the new component id.
+    ///
+    /// A component created with no entities is an orphan (`ref_counter == 0`),
+    /// it stays in this world until it is attached or reclaimed by [`gc`](Self::gc).
     pub fn new_component<V, I>(&mut self, value: V, entities: I) -> (Id, usize)
     where
         V: AsComponent + 'static,
@@ -238,10 +274,7 @@ impl World {
 
         self.component_types
             .get_mut(&component_type)
-            .expect(&format!(
-                "No system to handle component: {}",
-                component_type
-            ))
+            .expect(&format!("No system to handle component: {}", component_id))
             .insert(component_id, Component::new(value, ref_counter));
 
         (component_id, ref_counter)
@@ -261,10 +294,7 @@ impl World {
         if self
             .component_types
             .get_mut(&component_type)
-            .expect(&format!(
-                "No system to handle component: {}",
-                component_type
-            ))
+            .expect(&format!("No system to handle component: {}", component_id))
             .contains_key(component_id)
         {
             for entity_id in entities.into_iter() {
@@ -275,10 +305,7 @@ impl World {
 
             self.component_types
                 .get_mut(&component_type)
-                .expect(&format!(
-                    "No system to handle component: {}",
-                    component_type
-                ))
+                .expect(&format!("No system to handle component: {}", component_id))
                 .get_mut(component_id)
                 .unwrap()
                 .add_ref(ref_counter);
@@ -303,10 +330,7 @@ impl World {
         if self
             .component_types
             .get_mut(&component_type)
-            .expect(&format!(
-                "No system to handle component: {}",
-                component_type
-            ))
+            .expect(&format!("No system to handle component: {}", component_id))
             .contains_key(component_id)
         {
             for entity_id in entities.into_iter() {
@@ -317,16 +341,38 @@ impl World {
 
             self.component_types
                 .get_mut(&component_type)
-                .expect(&format!(
-                    "No system to handle component: {}",
-                    component_type
-                ))
+                .expect(&format!("No system to handle component: {}", component_id))
                 .get_mut(component_id)
                 .unwrap()
                 .dec_ref(ref_counter);
         }
     }
 
+    /// Remove all orphan components, which are not referenced by any entity.
+    ///
+    /// Returns the number of removed components.
+    pub fn gc(&mut self) -> usize {
+        let mut removed = 0;
+
+        for components in self.component_types.values_mut() {
+            let len = components.len();
+
+            components.retain(|_, component| component.ref_counter > 0);
+
+            removed += len - components.len();
+        }
+
+        removed
+    }
+
+    /// Returns the number of components in this world, including orphan components.
+    pub fn component_count(&self) -> usize {
+        self.component_types
+            .values()
+            .map(|components| components.len())
+            .sum()
+    }
+
     /// Get attached component id by component type.
     pub fn attached_component(&self, entity_id: &Id, component_type: &ComponentType) -> Option<Id> {
         assert_eq!(entity_id.reference_type(), ReferenceType::Entity);
@@ -352,10 +398,7 @@ impl World {
 
         self.component_types
             .get(&component_type)
-            .expect(&format!(
-                "No system to handle component: {}",
-                component_type
-            ))
+            .expect(&format!("No system to handle component: {}", component_id))
             .get(component_id)
             .map(|c| c.as_ref())
     }
@@ -378,10 +421,7 @@ impl World {
 
         self.component_types
             .get_mut(&component_type)
-            .expect(&format!(
-                "No system to handle component: {}",
-                component_type
-            ))
+            .expect(&format!("No system to handle component: {}", component_id))
             .get_mut(component_id)
             .map(|c| c.as_mut())
     }
@@ -455,4 +495,31 @@ mod tests {
 
         assert!(component.dec_ref(1));
     }
+
+    #[test]
+    fn test_gc() {
+        static C1: ComponentType = ComponentType::new(1);
+
+        let mut world = World::new(&[C1]);
+
+        let entity = world.new_entity();
+
+        let (orphan, ref_counter) = world.new_component_with(&C1, 1usize, None::<Id>);
+
+        assert_eq!(ref_counter, 0);
+
+        let (attached, _) = world.new_component_with(&C1, 2usize, [&entity]);
+
+        assert_eq!(world.component_count(), 2);
+
+        assert_eq!(world.gc(), 1);
+        assert_eq!(world.component_count(), 1);
+        assert_eq!(world.component_ref_unchecked::<usize>(&orphan), None);
+        assert_eq!(world.component_ref_unchecked::<usize>(&attached), Some(&2));
+
+        world.detach_component(&attached, [&entity]);
+
+        assert_eq!(world.gc(), 1);
+        assert_eq!(world.component_count(), 0);
+    }
 }
@@ -0,0 +1,296 @@
+use std::{any::Any, collections::HashMap};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{ComponentType, Error, Id, Result};
+
+use super::{Component, Entity, World};
+
+/// A component rust type that can be persisted in a [`WorldSnapshot`].
+///
+/// Components opt in by [`World::register_serializable`].
+pub trait SerializableComponent: Serialize + DeserializeOwned + 'static {}
+
+impl<T> SerializableComponent for T where T: Serialize + DeserializeOwned + 'static {}
+
+/// Type-erased serde functions of one component type.
+#[derive(Clone, Copy)]
+pub(super) struct ComponentSerializer {
+    /// Returns `None` if the value is not of the registered rust type.
+    serialize: fn(&dyn Any) -> Option<serde_json::Result<serde_json::Value>>,
+    deserialize: fn(serde_json::Value) -> serde_json::Result<Box<dyn Any>>,
+}
+
+impl ComponentSerializer {
+    fn new<V>() -> Self
+    where
+        V: SerializableComponent,
+    {
+        fn serialize<V: SerializableComponent>(
+            value: &dyn Any,
+        ) -> Option<serde_json::Result<serde_json::Value>> {
+            value.downcast_ref::<V>().map(serde_json::to_value)
+        }
+
+        fn deserialize<V: SerializableComponent>(
+            value: serde_json::Value,
+        ) -> serde_json::Result<Box<dyn Any>> {
+            Ok(Box::new(serde_json::from_value::<V>(value)?))
+        }
+
+        Self {
+            serialize: serialize::<V>,
+            deserialize: deserialize::<V>,
+        }
+    }
+}
+
+/// A portable snapshot of [`World`], created by [`World::snapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorldSnapshot {
+    /// The [`schema_hash`](World::schema_hash) of the snapshotted world.
+    pub schema_hash: u64,
+    /// The next sequence number.
+    pub idgen: u64,
+    /// entity id and attached component ids.
+    pub entities: Vec<(Id, Vec<Id>)>,
+    /// serialized components.
+    pub components: Vec<ComponentSnapshot>,
+}
+
+/// A serialized component in [`WorldSnapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentSnapshot {
+    /// The component id.
+    pub id: Id,
+    /// How many entities reference this component.
+    pub ref_counter: usize,
+    /// The serialized value.
+    pub value: serde_json::Value,
+}
+
+impl World {
+    /// Register component type `V` as serializable, so that it is persisted by [`snapshot`](Self::snapshot).
+    ///
+    /// `V` must be the rust type of the components of `component_type`,
+    /// otherwise [`snapshot`](Self::snapshot) returns [`Error::ComponentTypeMismatch`].
+    pub fn register_serializable<V>(&mut self, component_type: &ComponentType)
+    where
+        V: SerializableComponent,
+    {
+        self.serializers
+            .insert(*component_type, ComponentSerializer::new::<V>());
+    }
+
+    /// Create a portable snapshot of this world.
+    ///
+    /// Components that are not [registered](Self::register_serializable) as serializable are skipped with a warning,
+    /// and are also removed from the entity component lists of the snapshot.
+    pub fn snapshot(&self) -> Result<WorldSnapshot> {
+        let mut components = vec![];
+
+        for (component_type, values) in &self.component_types {
+            let Some(serializer) = self.serializers.get(component_type) else {
+                if !values.is_empty() {
+                    log::warn!(
+                        "World snapshot, skip {} components of non-serializable type: {}",
+                        values.len(),
+                        component_type
+                    );
+                }
+
+                continue;
+            };
+
+            for (id, component) in values {
+                let value = (serializer.serialize)(component.value.as_ref())
+                    .ok_or(Error::ComponentTypeMismatch(*component_type))??;
+
+                components.push(ComponentSnapshot {
+                    id: *id,
+                    ref_counter: component.ref_counter,
+                    value,
+                });
+            }
+        }
+
+        components.sort_by_key(|component| component.id);
+
+        let mut entities = self
+            .entities
+            .iter()
+            .map(|(id, entity)| {
+                let component_ids = entity
+                    .0
+                    .iter()
+                    .filter(|id| self.serializers.contains_key(&id.component_type()))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                (*id, component_ids)
+            })
+            .collect::<Vec<_>>();
+
+        entities.sort_by_key(|(id, _)| *id);
+
+        Ok(WorldSnapshot {
+            schema_hash: self.schema_hash,
+            idgen: self.idgen,
+            entities,
+            components,
+        })
+    }
+
+    /// Restore a new world from the `snapshot` content.
+    ///
+    /// This world is used as the template of the restored world: the restored world has the component types,
+    /// the [`schema_hash`](Self::schema_hash) and the [serializers](Self::register_serializable) of this world,
+    /// but none of its entities and components.
+    ///
+    /// This world must have the same [`schema_hash`](Self::schema_hash) as the snapshotted world,
+    /// all the snapshotted component types must be [registered](Self::register_serializable) as serializable,
+    /// and the component ids of snapshotted entities must refer to snapshotted components.
+    pub fn restore(&self, snapshot: WorldSnapshot) -> Result<World> {
+        if snapshot.schema_hash != self.schema_hash {
+            return Err(Error::SchemaMismatch {
+                expect: self.schema_hash,
+                got: snapshot.schema_hash,
+            });
+        }
+
+        let mut component_types: HashMap<ComponentType, HashMap<Id, Component>> = self
+            .component_types
+            .keys()
+            .map(|component_type| (*component_type, Default::default()))
+            .collect();
+
+        for component in snapshot.components {
+            let component_type = component.id.component_type();
+
+            let (Some(values), Some(serializer)) = (
+                component_types.get_mut(&component_type),
+                self.serializers.get(&component_type),
+            ) else {
+                return Err(Error::UnknownComponentType(component_type));
+            };
+
+            values.insert(
+                component.id,
+                Component {
+                    ref_counter: component.ref_counter,
+                    value: (serializer.deserialize)(component.value)?,
+                },
+            );
+        }
+
+        for (_, component_ids) in &snapshot.entities {
+            for id in component_ids {
+                if !component_types
+                    .get(&id.component_type())
+                    .is_some_and(|values| values.contains_key(id))
+                {
+                    return Err(Error::UnknownComponent(*id));
+                }
+            }
+        }
+
+        Ok(World {
+            idgen: snapshot.idgen,
+            entities: snapshot
+                .entities
+                .into_iter()
+                .map(|(id, component_ids)| (id, Entity(component_ids)))
+                .collect(),
+            component_types,
+            schema_hash: self.schema_hash,
+            serializers: self.serializers.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot() {
+        static C1: ComponentType = ComponentType::new(1);
+        static C2: ComponentType = ComponentType::new(2);
+
+        let mut world = World::new(&[C1, C2]);
+
+        world.register_serializable::<usize>(&C1);
+
+        let entity = world.new_entity();
+
+        let (component_id, _) = world.new_component_with(&C1, 42usize, [&entity]);
+
+        // not serializable.
+        world.new_component_with(&C2, "hello", [&entity]);
+
+        let snapshot = world.snapshot().unwrap();
+
+        assert_eq!(snapshot.entities, vec![(entity, vec![component_id])]);
+
+        // snapshot is portable.
+        let snapshot: WorldSnapshot =
+            serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        let mut template = World::new(&[C1, C2]);
+
+        template.register_serializable::<usize>(&C1);
+
+        let mut restored = template.restore(snapshot.clone()).unwrap();
+
+        assert_eq!(
+            restored.attached_component(&entity, &C1),
+            Some(component_id)
+        );
+        assert_eq!(restored.attached_component(&entity, &C2), None);
+
+        assert_eq!(
+            restored.component_ref_unchecked::<usize>(&component_id),
+            Some(&42)
+        );
+
+        // new ids don't conflict with the restored ones.
+        assert_ne!(restored.new_entity(), entity);
+
+        // the restored world can be snapshotted again.
+        assert_eq!(restored.snapshot().unwrap().components, snapshot.components);
+
+        // the template is left untouched.
+        assert_eq!(template.attached_component(&entity, &C1), None);
+
+        assert!(matches!(
+            World::new(&[C1]).restore(snapshot.clone()),
+            Err(Error::SchemaMismatch { .. })
+        ));
+
+        // dangling component id.
+        let mut dangling = snapshot;
+
+        dangling.components.clear();
+
+        assert!(matches!(
+            template.restore(dangling),
+            Err(Error::UnknownComponent(id)) if id == component_id
+        ));
+    }
+
+    #[test]
+    fn test_snapshot_type_mismatch() {
+        static C1: ComponentType = ComponentType::new(1);
+
+        let mut world = World::new(&[C1]);
+
+        world.register_serializable::<String>(&C1);
+
+        world.new_component_with(&C1, 42usize, None::<Id>);
+
+        assert!(matches!(
+            world.snapshot(),
+            Err(Error::ComponentTypeMismatch(component_type)) if component_type == C1
+        ));
+    }
+}
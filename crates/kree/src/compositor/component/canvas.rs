@@ -1,4 +1,6 @@
 use ecsrs::Id;
+use euclid::default::Box2D;
+use wgpu::Buffer;
 use wgpu_derive::Vertex;
 
 #[repr(C)]
@@ -12,3 +14,17 @@ pub struct Canvas2DVertex {
 pub struct Canvas2DComponent {
     pub id: Id,
 }
+
+/// The GPU geometry of a 2d canvas, drawn by [`CanvasSystem`](crate::compositor::CanvasSystem).
+pub struct Canvas2DGeometryComponent {
+    /// vertex buffer of [`Canvas2DVertex`]s.
+    pub vertex_buffer: Buffer,
+    /// index buffer of `u32` indices.
+    pub index_buffer: Buffer,
+    /// The number of indices.
+    pub index_count: u32,
+    /// The bounds of the vertices in normalized device coordinates.
+    pub bounds: Box2D<f32>,
+    /// The bounds of the replaced geometries that have not been redrawn yet.
+    pub replaced_bounds: Option<Box2D<f32>>,
+}
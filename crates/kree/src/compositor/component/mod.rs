@@ -20,5 +20,6 @@ ecsrs::ecs_system!(
     LayerComponent,
     RedrawComponent,
     Canvas2DComponent,
-    CaptureComponent
+    CaptureComponent,
+    Canvas2DGeometryComponent
 );
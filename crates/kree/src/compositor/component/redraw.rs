@@ -1,4 +1,6 @@
 use ecsrs::Id;
 
 /// A component that contains redraw entity list.
+///
+/// The entities changed since the last frame, the next frame redraws the region covered by them.
 pub struct RedrawComponent(pub Vec<Id>);
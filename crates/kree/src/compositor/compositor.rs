@@ -1,35 +1,126 @@
 use std::{
     ops::{Deref, DerefMut},
     sync::mpsc,
+    time::{Duration, Instant},
 };
 
-use ecsrs::{AsComponent, Id};
+use ecsrs::{AsComponent, ComponentType, Id};
 
 use wgpu::{
-    Adapter, Buffer, CommandBuffer, CommandEncoder, CommandEncoderDescriptor, Device, Extent3d,
-    Queue, RenderPass, ShaderSource, Surface, SurfaceTarget, Texture, TextureDescriptor,
+    Adapter, Buffer, Color, CommandBuffer, CommandEncoder, CommandEncoderDescriptor, Device,
+    Extent3d, LoadOp, PowerPreference, PresentMode, Queue, RenderPass, ShaderSource, Surface,
+    SurfaceTarget, Texture, TextureDescriptor, TextureFormat, TextureView,
 };
 
+use euclid::default::Box2D;
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
 use crate::{
-    compositor::{Canvas2DComponent, CaptureComponent, LayerComponent, RedrawComponent},
-    BufferSizeOf, Error, Png, Result, Viewport,
+    aligned_bytes_per_row,
+    compositor::{registered_types, Canvas2DComponent, RedrawComponent, SCHEMA_HASH},
+    BufferSizeOf, ColorSpace, Error, Png, Rect, Result, Viewport,
 };
 
-use super::{RenderSystem, SvgSystem};
+use super::{Canvas2DGeometryComponent, Canvas2DVertex, CanvasSystem, RenderSystem, SvgSystem};
+
+/// A factory of custom [`RenderSystem`], called with the device, the target format
+/// and the resolved msaa sample count of the [`Compositor`].
+pub type RenderSystemFactory =
+    Box<dyn FnOnce(&Device, TextureFormat, u32) -> Box<dyn RenderSystem>>;
 
 /// A builder for graphics [`Compositor`]
 pub struct CompositorBuilder {
     svg_shader_source: ShaderSource<'static>,
+    /// user registered systems.
+    systems: Vec<RenderSystemFactory>,
+    /// component types required by user registered systems.
+    component_types: Vec<ComponentType>,
+    /// requested msaa sample count.
+    sample_count: u32,
+    /// color space of captured pixels.
+    color_space: ColorSpace,
+    /// power preference of the requested adapter.
+    power_preference: PowerPreference,
 }
 
 impl CompositorBuilder {
     fn new() -> Self {
         Self {
             svg_shader_source: ShaderSource::Wgsl(include_str!("./system/shader/svg.wgsl").into()),
+            systems: vec![],
+            component_types: vec![],
+            sample_count: 1,
+            color_space: Default::default(),
+            power_preference: PowerPreference::None,
         }
     }
 
-    async fn create_wgpu() -> Result<(Device, Queue)> {
+    /// Set the multisample count of the render target, the default value is `1` (no msaa).
+    ///
+    /// If the adapter doesn't support the requested count for the target format,
+    /// the compositor falls back to `1`.
+    pub fn sample_count(mut self, count: u32) -> Self {
+        self.sample_count = count;
+        self
+    }
+
+    /// Set the color space of captured pixels, the default value is [`ColorSpace::Srgb`].
+    ///
+    /// This affects [`TextureCompositor::compositing`], [`TextureCompositor::compositing_into`]
+    /// and [`SurfaceCompositor::capture`].
+    pub fn color_space(mut self, color_space: ColorSpace) -> Self {
+        self.color_space = color_space;
+        self
+    }
+
+    /// Set the power preference used to request the adapter, the default value is [`PowerPreference::None`].
+    ///
+    /// On a machine with multiple GPUs, e.g. a laptop, this selects the integrated ([`LowPower`](PowerPreference::LowPower))
+    /// or discrete ([`HighPerformance`](PowerPreference::HighPerformance)) GPU.
+    pub fn power_preference(mut self, power_preference: PowerPreference) -> Self {
+        self.power_preference = power_preference;
+        self
+    }
+
+    /// Register a custom [`RenderSystem`] and the component types it needs.
+    ///
+    /// Custom systems run after the builtin systems, in registration order.
+    ///
+    /// The requested [`sample_count`](Self::sample_count) may fall back to `1`, a system that creates
+    /// render pipelines should be registered via [`with_system_factory`](Self::with_system_factory) instead.
+    pub fn with_system<I>(self, system: Box<dyn RenderSystem>, component_types: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<ComponentType>,
+    {
+        self.with_system_factory(Box::new(move |_, _, _| system), component_types)
+    }
+
+    /// Register a custom [`RenderSystem`] created by `factory` and the component types it needs.
+    ///
+    /// `factory` is called on creating the [`Compositor`] with the target format and the resolved msaa sample count,
+    /// which the pipelines of the system must match.
+    pub fn with_system_factory<I>(
+        mut self,
+        factory: RenderSystemFactory,
+        component_types: I,
+    ) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<ComponentType>,
+    {
+        self.systems.push(factory);
+
+        self.component_types.extend(
+            component_types
+                .into_iter()
+                .map(|component_type| *component_type.as_ref()),
+        );
+
+        self
+    }
+
+    async fn create_wgpu(power_preference: PowerPreference) -> Result<(Device, Queue, Adapter)> {
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
             backends: wgpu::Backends::all(),
             ..Default::default()
@@ -37,14 +128,14 @@ impl CompositorBuilder {
 
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptionsBase {
-                power_preference: wgpu::PowerPreference::None,
+                power_preference,
                 force_fallback_adapter: false,
                 compatible_surface: None,
             })
             .await
             .ok_or(Error::RequestAdapterError)?;
 
-        Ok(adapter
+        let (device, queue) = adapter
             .request_device(
                 &wgpu::DeviceDescriptor {
                     label: Some("WgpuCompositor"),
@@ -53,10 +144,13 @@ impl CompositorBuilder {
                 },
                 None,
             )
-            .await?)
+            .await?;
+
+        Ok((device, queue, adapter))
     }
 
     async fn create_wgpu_with<'window>(
+        power_preference: PowerPreference,
         target: impl Into<SurfaceTarget<'window>>,
     ) -> Result<(Device, Queue, Adapter, Surface<'window>)> {
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
@@ -68,7 +162,7 @@ impl CompositorBuilder {
 
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptionsBase {
-                power_preference: wgpu::PowerPreference::None,
+                power_preference,
                 force_fallback_adapter: false,
                 compatible_surface: Some(&surface),
             })
@@ -89,25 +183,69 @@ impl CompositorBuilder {
         Ok((device, queue, adapter, surface))
     }
 
-    async fn create(self, device: Device, queue: Queue) -> Result<Compositor> {
-        let world = ecsrs::World::new([
-            LayerComponent::component_type(),
-            RedrawComponent::component_type(),
-            Canvas2DComponent::component_type(),
-            CaptureComponent::component_type(),
-        ]);
+    /// Returns the requested sample count if supported by `adapter`, otherwise returns `1`.
+    fn validate_sample_count(&self, adapter: &Adapter, format: TextureFormat) -> u32 {
+        let flags = adapter.get_texture_format_features(format).flags;
 
-        let systems: Vec<Box<dyn RenderSystem>> = vec![Box::new(SvgSystem::with_shader(
-            Some("Svg"),
-            &device,
-            self.svg_shader_source,
-        ))];
+        if flags.sample_count_supported(self.sample_count) {
+            self.sample_count
+        } else {
+            log::warn!(
+                "CompositorBuilder, sample count {} is not supported by format {:?}, fallback to 1",
+                self.sample_count,
+                format
+            );
+
+            1
+        }
+    }
+
+    async fn create(
+        self,
+        device: Device,
+        queue: Queue,
+        adapter: &Adapter,
+        format: TextureFormat,
+    ) -> Result<Compositor> {
+        let sample_count = self.validate_sample_count(adapter, format);
+
+        let mut world = ecsrs::World::new_with_schema(
+            registered_types().iter().chain(self.component_types.iter()),
+            SCHEMA_HASH,
+        );
+
+        let (redraw, _) = world.new_component(RedrawComponent(vec![]), None::<Id>);
+
+        let mut systems: Vec<Box<dyn RenderSystem>> = vec![
+            Box::new(SvgSystem::with_shader(
+                Some("Svg"),
+                &device,
+                self.svg_shader_source,
+                sample_count,
+            )),
+            Box::new(CanvasSystem::new(
+                Some("Canvas"),
+                &device,
+                format,
+                sample_count,
+            )),
+        ];
+
+        for factory in self.systems {
+            systems.push(factory(&device, format, sample_count));
+        }
 
         Ok(Compositor {
             world,
+            redraw,
             device,
             queue,
             systems,
+            format,
+            sample_count,
+            color_space: self.color_space,
+            stats: Default::default(),
+            readback_buffers: 0,
         })
     }
 
@@ -117,27 +255,37 @@ impl CompositorBuilder {
         target: impl Into<SurfaceTarget<'window>>,
         viewport: Viewport,
     ) -> Result<SurfaceCompositor<'window>> {
-        let (device, queue, adapter, surface) = Self::create_wgpu_with(target).await?;
-        let rendering = self.create(device, queue).await?;
+        let (device, queue, adapter, surface) =
+            Self::create_wgpu_with(self.power_preference, target).await?;
 
         let config = surface
             .get_default_config(&adapter, viewport.width, viewport.height)
             .unwrap();
 
+        let rendering = self.create(device, queue, &adapter, config.format).await?;
+
         surface.configure(&rendering.device, &config);
 
+        let msaa_target = rendering.create_msaa_target(&viewport, config.format);
+
         Ok(SurfaceCompositor {
             surface,
+            adapter,
             rendering,
             config,
+            viewport,
+            msaa_target,
+            dirty: DirtyRegion::full(&viewport),
         })
     }
 
     /// Create a [`Compositor`] whose rendering target is a GPU texture.
     pub async fn render_to_texture(self, viewport: Viewport) -> Result<TextureCompositor> {
-        let (device, queue) = Self::create_wgpu().await?;
+        let (device, queue, adapter) = Self::create_wgpu(self.power_preference).await?;
 
-        let rendering = self.create(device, queue).await?;
+        let mut rendering = self
+            .create(device, queue, &adapter, TextureFormat::Rgba8UnormSrgb)
+            .await?;
 
         let texture_target = rendering.device.create_texture(&TextureDescriptor {
             size: wgpu::Extent3d {
@@ -154,17 +302,18 @@ impl CompositorBuilder {
             view_formats: &[wgpu::TextureFormat::Rgba8UnormSrgb],
         });
 
-        let buffer = rendering.device.create_buffer(&wgpu::BufferDescriptor {
-            size: viewport.buffer_size_of(),
-            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-            label: Some("TextureCompositor"),
-            mapped_at_creation: false,
-        });
+        let buffer = rendering.create_readback_buffer(&viewport, "TextureCompositor");
+
+        let msaa_target =
+            rendering.create_msaa_target(&viewport, wgpu::TextureFormat::Rgba8UnormSrgb);
 
         Ok(TextureCompositor {
             buffer,
             texture_target,
             rendering,
+            viewport,
+            msaa_target,
+            dirty: DirtyRegion::full(&viewport),
         })
     }
 }
@@ -174,12 +323,41 @@ impl CompositorBuilder {
 pub struct Compositor {
     /// rendering ecs world.
     world: ecsrs::World,
+    /// id of the [`RedrawComponent`] that lists the entities changed since the last frame.
+    redraw: Id,
     /// Associated wgpu Device.
     device: Device,
     /// Associated wgpu Queue.
     queue: Queue,
     /// system for svg component.
     systems: Vec<Box<dyn RenderSystem>>,
+    /// format of render target.
+    format: TextureFormat,
+    /// msaa sample count of render target.
+    sample_count: u32,
+    /// color space of captured pixels.
+    color_space: ColorSpace,
+    /// timing statistics of the last frame.
+    stats: CompositorStats,
+    /// The number of GPU readback buffers created by this compositor.
+    readback_buffers: usize,
+}
+
+/// The CPU time spent by each phase of one [`compositing`](TextureCompositor::compositing) call.
+///
+/// GPU work is executed asynchronously, so `redraw`/`composite` only measure the command recording.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompositorStats {
+    /// time spent by [`RenderSystem::prepare`] of all systems.
+    pub prepare: Duration,
+    /// time spent by [`RenderSystem::redraw`] of all systems.
+    pub redraw: Duration,
+    /// time spent by [`RenderSystem::composite`] of all systems.
+    pub composite: Duration,
+    /// time spent by submitting command buffers to the queue.
+    pub submit: Duration,
+    /// time spent by the whole `compositing` call.
+    pub frame: Duration,
 }
 
 impl Compositor {
@@ -199,29 +377,393 @@ impl Compositor {
 
         id
     }
+
+    /// Upload the triangle list geometry of `canvas`, replacing the previous one.
+    ///
+    /// The vertex positions are in normalized device coordinates, triangles are counter-clockwise.
+    /// The region covered by the previous and the new geometry is redrawn in the next frame.
+    pub fn set_canvas_geometry(
+        &mut self,
+        canvas: &Id,
+        vertices: &[Canvas2DVertex],
+        indices: &[u32],
+    ) {
+        let mut geometry = Canvas2DGeometryComponent {
+            vertex_buffer: self.device.create_buffer_init(&BufferInitDescriptor {
+                label: Some("Canvas2DVertex"),
+                contents: bytemuck::cast_slice(vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            }),
+            index_buffer: self.device.create_buffer_init(&BufferInitDescriptor {
+                label: Some("Canvas2DIndex"),
+                contents: bytemuck::cast_slice(indices),
+                usage: wgpu::BufferUsages::INDEX,
+            }),
+            index_count: indices.len() as u32,
+            bounds: Box2D::from_points(
+                vertices
+                    .iter()
+                    .map(|vertex| euclid::point2(vertex.position[0], vertex.position[1])),
+            ),
+            replaced_bounds: None,
+        };
+
+        if let Some(component_id) = self
+            .world
+            .attached_component(canvas, Canvas2DGeometryComponent::component_type())
+        {
+            let current = self
+                .world
+                .component_mut::<Canvas2DGeometryComponent>(&component_id)
+                .unwrap();
+
+            geometry.replaced_bounds = Some(
+                current
+                    .replaced_bounds
+                    .map_or(current.bounds, |bounds| bounds.union(&current.bounds)),
+            );
+
+            *current = geometry;
+        } else {
+            self.world.new_component(geometry, [canvas]);
+        }
+
+        let redraw = &mut self
+            .world
+            .component_mut::<RedrawComponent>(&self.redraw)
+            .unwrap()
+            .0;
+
+        if !redraw.contains(canvas) {
+            redraw.push(*canvas);
+        }
+    }
+
+    /// Returns the format of the render target.
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    /// Returns the msaa sample count of the render target,
+    /// `1` if msaa is disabled or the requested count is not supported.
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Returns the timing statistics of the last `compositing` call,
+    /// all durations are zero before the first frame.
+    pub fn last_stats(&self) -> CompositorStats {
+        self.stats
+    }
+
+    /// Returns the number of GPU readback buffers created by this compositor.
+    ///
+    /// [`TextureCompositor`] creates one persistent buffer, [`SurfaceCompositor::capture`] creates one per call.
+    pub fn readback_buffers(&self) -> usize {
+        self.readback_buffers
+    }
 }
 
 impl Compositor {
+    /// Create a multisampled render target, returns `None` if msaa is disabled.
+    fn create_msaa_target(&self, viewport: &Viewport, format: TextureFormat) -> Option<Texture> {
+        if self.sample_count == 1 {
+            return None;
+        }
+
+        Some(self.device.create_texture(&TextureDescriptor {
+            size: wgpu::Extent3d {
+                width: viewport.width,
+                height: viewport.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: self.sample_count,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            label: Some("MsaaTarget"),
+            view_formats: &[],
+        }))
+    }
+
+    /// Mark the regions of `viewport` covered by the entities of [`RedrawComponent`] as dirty and clear the entity list.
+    fn take_redraw(&mut self, viewport: &Viewport, dirty: &mut DirtyRegion) {
+        let entities = std::mem::take(
+            &mut self
+                .world
+                .component_mut::<RedrawComponent>(&self.redraw)
+                .unwrap()
+                .0,
+        );
+
+        for entity in entities {
+            let Some(component_id) = self
+                .world
+                .attached_component(&entity, Canvas2DGeometryComponent::component_type())
+            else {
+                continue;
+            };
+
+            let geometry = self
+                .world
+                .component_mut::<Canvas2DGeometryComponent>(&component_id)
+                .unwrap();
+
+            let bounds = geometry
+                .replaced_bounds
+                .take()
+                .map_or(geometry.bounds, |bounds| bounds.union(&geometry.bounds));
+
+            dirty.mark(ndc_to_rect(&bounds, viewport));
+        }
+    }
+
+    /// Create a GPU buffer that the texture of `viewport` dimensions can be copied into and read back from.
+    fn create_readback_buffer(&mut self, viewport: &Viewport, label: &str) -> Buffer {
+        self.readback_buffers += 1;
+
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            size: viewport.buffer_size_of(),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            label: Some(label),
+            mapped_at_creation: false,
+        })
+    }
+
     fn prepare(&mut self, viewport: &Viewport, command_encoder: &mut CommandEncoder) {
+        let start = Instant::now();
+
         for system in &self.systems {
             system.prepare(&mut self.world, viewport, command_encoder);
         }
+
+        self.stats.prepare = start.elapsed();
     }
 
     fn redraw<'a>(&mut self, viewport: &Viewport, render_pass: &mut RenderPass<'a>) {
+        let start = Instant::now();
+
         for system in &self.systems {
             system.redraw(&mut self.world, viewport, render_pass);
         }
+
+        self.stats.redraw = start.elapsed();
     }
 
     fn composite(&mut self, viewport: &Viewport, command_encoder: &mut CommandEncoder) {
+        let start = Instant::now();
+
         for system in self.systems.iter().rev() {
-            system.prepare(&mut self.world, viewport, command_encoder);
+            system.composite(&mut self.world, viewport, command_encoder);
         }
+
+        self.stats.composite = start.elapsed();
     }
 
     fn submit<I: IntoIterator<Item = CommandBuffer>>(&mut self, command_buffers: I) {
+        let start = Instant::now();
+
         self.queue.submit(command_buffers);
+
+        self.stats.submit = start.elapsed();
+    }
+
+    /// Record one frame of all systems into `view`, resolving `msaa_view` into `view` if msaa is enabled.
+    fn render(
+        &mut self,
+        viewport: &Viewport,
+        command_encoder: &mut CommandEncoder,
+        view: &TextureView,
+        msaa_view: Option<&TextureView>,
+        load: LoadOp<Color>,
+        scissor: Option<Rect>,
+    ) {
+        self.prepare(viewport, command_encoder);
+
+        {
+            let mut render_pass = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Compositor"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: msaa_view.unwrap_or(view),
+                    resolve_target: msaa_view.map(|_| view),
+                    ops: wgpu::Operations {
+                        load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            if let Some(scissor) = scissor {
+                render_pass.set_scissor_rect(
+                    scissor.origin.x,
+                    scissor.origin.y,
+                    scissor.size.width,
+                    scissor.size.height,
+                );
+            }
+
+            self.redraw(viewport, &mut render_pass);
+        }
+
+        self.composite(viewport, command_encoder);
+    }
+
+    /// Render a frame of `viewport` dimensions into an offscreen texture with the target format and read it back.
+    ///
+    /// `msaa_target` is the multisampled target of the caller, it is cleared and redrawn entirely.
+    /// The timing statistics of the last frame are kept.
+    fn capture(
+        &mut self,
+        viewport: &Viewport,
+        msaa_target: Option<&Texture>,
+        clear: Color,
+    ) -> Result<Png<'static>> {
+        let format = self.format;
+
+        let bgra = match format {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+            _ => return Err(Error::UnsupportedCaptureFormat(format)),
+        };
+
+        let texture = self.device.create_texture(&TextureDescriptor {
+            size: wgpu::Extent3d {
+                width: viewport.width,
+                height: viewport.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            label: Some("Compositor capture"),
+            view_formats: &[],
+        });
+
+        let buffer = self.create_readback_buffer(viewport, "Compositor capture");
+
+        let mut command_encoder = self
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("Compositor capture"),
+            });
+
+        let texture_view = texture.create_view(&Default::default());
+
+        let msaa_view = msaa_target.map(|texture| texture.create_view(&Default::default()));
+
+        let stats = self.stats;
+
+        self.render(
+            viewport,
+            &mut command_encoder,
+            &texture_view,
+            msaa_view.as_ref(),
+            LoadOp::Clear(clear),
+            None,
+        );
+
+        Self::copy_to_buffer(&mut command_encoder, &texture, &buffer);
+
+        self.submit([command_encoder.finish()]);
+
+        self.stats = stats;
+
+        let mut image_data = self.read_buffer(&buffer, viewport, format)?;
+
+        if bgra {
+            bgra_to_rgba(&mut image_data);
+        }
+
+        to_png(image_data, *viewport)
+    }
+
+    /// Record a copy of the whole `texture` into `buffer`, rows are padded to [`aligned_bytes_per_row`].
+    fn copy_to_buffer(command_encoder: &mut CommandEncoder, texture: &Texture, buffer: &Buffer) {
+        command_encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                aspect: wgpu::TextureAspect::All,
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(aligned_bytes_per_row(texture.width())),
+                    rows_per_image: Some(texture.height()),
+                },
+            },
+            Extent3d {
+                width: texture.width(),
+                height: texture.height(),
+                depth_or_array_layers: 1,
+            },
+        );
+    }
+
+    /// Wait for `buffer` to be mapped and read back the pixels of `viewport` dimensions.
+    ///
+    /// `format` is the format of the copied texture, the pixels are converted into the [`ColorSpace`] of this compositor.
+    fn read_buffer(
+        &self,
+        buffer: &Buffer,
+        viewport: &Viewport,
+        format: TextureFormat,
+    ) -> Result<Vec<u8>> {
+        let mut image_data = vec![];
+
+        self.read_buffer_into(buffer, viewport, format, &mut image_data)?;
+
+        Ok(image_data)
+    }
+
+    /// Like [`read_buffer`](Self::read_buffer), but replaces the content of `image_data` with the pixels.
+    fn read_buffer_into(
+        &self,
+        buffer: &Buffer,
+        viewport: &Viewport,
+        format: TextureFormat,
+        image_data: &mut Vec<u8>,
+    ) -> Result<()> {
+        let (sender, receiver) = mpsc::channel();
+
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                log::trace!("Compositor, buffer map_async: {:?}", result);
+                _ = sender.send(result);
+            });
+
+        self.device.poll(wgpu::MaintainBase::Wait);
+
+        receiver.recv().unwrap()?;
+
+        let view = buffer.slice(..).get_mapped_range();
+
+        // strip the row padding required by `copy_texture_to_buffer`.
+        let bytes_per_row = size_of::<u32>() * viewport.width as usize;
+
+        image_data.clear();
+
+        for row in view.chunks(aligned_bytes_per_row(viewport.width) as usize) {
+            image_data.extend_from_slice(&row[..bytes_per_row]);
+        }
+
+        drop(view);
+        buffer.unmap();
+
+        if self.color_space == ColorSpace::Linear && format.is_srgb() {
+            srgb_to_linear(image_data);
+        }
+
+        Ok(())
     }
 }
 
@@ -230,10 +772,70 @@ pub struct SurfaceCompositor<'window> {
     #[allow(unused)]
     /// surface associated with the window.
     surface: Surface<'window>,
+    /// adapter of the surface device, used to query surface capabilities.
+    adapter: Adapter,
     /// rendeing system
     rendering: Compositor,
     /// surface configuration.
     config: wgpu::SurfaceConfiguration,
+    /// viewport of the surface.
+    viewport: Viewport,
+    /// multisampled render target, resolved into the surface texture.
+    msaa_target: Option<Texture>,
+    /// The region that needs to be redrawn in next frame.
+    dirty: DirtyRegion,
+}
+
+/// Accumulated dirty rects of a render target.
+#[derive(Debug, Default)]
+struct DirtyRegion(Option<Rect>);
+
+impl DirtyRegion {
+    /// Create a region covering the whole `viewport`, i.e. the next frame is redrawn entirely.
+    fn full(viewport: &Viewport) -> Self {
+        Self(Some(viewport.as_rect()))
+    }
+
+    /// Add `rect` into this region.
+    fn mark(&mut self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+
+        self.0 = Some(match self.0 {
+            Some(region) => region.union(&rect),
+            None => rect,
+        });
+    }
+
+    /// Returns the scissor rect of next frame and resets this region.
+    ///
+    /// Returns `None` if no region is marked or the marked region covers the whole `viewport`, which means a full redraw.
+    fn take_scissor(&mut self, viewport: &Viewport) -> Option<Rect> {
+        let scissor = viewport.clamp_rect(self.0.take()?);
+
+        if scissor.is_empty() || scissor == viewport.as_rect() {
+            None
+        } else {
+            Some(scissor)
+        }
+    }
+
+    /// Returns the load op and scissor rect of next frame and resets this region.
+    ///
+    /// A partial redraw loads the contents of the previous frame, so it is only used if the target is `preserved`
+    /// between frames, otherwise the whole target is cleared with `clear` and redrawn.
+    fn take_frame(
+        &mut self,
+        viewport: &Viewport,
+        preserved: bool,
+        clear: Color,
+    ) -> (LoadOp<Color>, Option<Rect>) {
+        match self.take_scissor(viewport) {
+            Some(scissor) if preserved => (LoadOp::Load, Some(scissor)),
+            _ => (LoadOp::Clear(clear), None),
+        }
+    }
 }
 
 impl<'window> Deref for SurfaceCompositor<'window> {
@@ -250,10 +852,25 @@ impl<'window> DerefMut for SurfaceCompositor<'window> {
 }
 
 impl<'a> SurfaceCompositor<'a> {
+    /// Mark a region of the surface in physical pixels as dirty.
+    ///
+    /// If msaa is enabled, the next [`compositing`](Self::compositing) only redraws the union of all dirty rects,
+    /// the rest of the multisampled target (owned by this compositor) keeps the contents of the previous frame.
+    /// Canvases changed by [`set_canvas_geometry`](Compositor::set_canvas_geometry) are marked dirty automatically.
+    /// Without any dirty rect, the whole surface is redrawn.
+    ///
+    /// Note: without msaa every frame is cleared and redrawn entirely, because the contents of
+    /// a presented surface texture are undefined.
+    pub fn mark_dirty(&mut self, rect: Rect) {
+        self.dirty.mark(rect);
+    }
+
     /// Invoke a rendering process and composite all rendering layer into the surface target.
     ///
     /// On success, this fn will call [`present`](wgpu::SurfaceTexture::present) internally.
     pub fn compositing(&mut self) -> Result<()> {
+        let frame = Instant::now();
+
         let texture = self.surface.get_current_texture()?;
 
         let mut command_encoder =
@@ -263,87 +880,276 @@ impl<'a> SurfaceCompositor<'a> {
                     label: Some("TextureCompositor"),
                 });
 
-        let viewport = Viewport::new(texture.texture.width(), texture.texture.height());
+        let viewport = self.viewport;
 
         let texture_view = texture.texture.create_view(&Default::default());
 
-        self.prepare(&viewport, &mut command_encoder);
+        let msaa_view = self
+            .msaa_target
+            .as_ref()
+            .map(|texture| texture.create_view(&Default::default()));
 
-        {
-            let mut render_pass = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("TextureCompositor"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &texture_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 1.0,
-                            g: 1.0,
-                            b: 1.0,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
+        self.rendering.take_redraw(&viewport, &mut self.dirty);
 
-            self.redraw(&viewport, &mut render_pass);
-        }
+        let (load, scissor) =
+            self.dirty
+                .take_frame(&viewport, self.msaa_target.is_some(), SURFACE_CLEAR_COLOR);
 
-        self.composite(&viewport, &mut command_encoder);
+        self.rendering.render(
+            &viewport,
+            &mut command_encoder,
+            &texture_view,
+            msaa_view.as_ref(),
+            load,
+            scissor,
+        );
 
         self.submit([command_encoder.finish()]);
 
         texture.present();
 
+        self.rendering.stats.frame = frame.elapsed();
+
         Ok(())
     }
 
     /// Resize the surface's viewport.
+    ///
+    /// The physical dimensions of `viewport` are used as-is (e.g. a window's `PhysicalSize`),
+    /// the scale factor of this compositor is preserved, see [`set_scale_factor`](Self::set_scale_factor).
     pub fn resize(&mut self, viewport: Viewport) {
+        let viewport = Viewport::physical(
+            viewport.width,
+            viewport.height,
+            self.viewport.scale_factor(),
+        );
+
         if viewport.width == 0 || viewport.height == 0 {
             log::warn!("SurfaceCompositor, resize with invalid data: {}", viewport);
             return;
         }
 
+        self.viewport = viewport;
+
         self.config.width = viewport.width;
         self.config.height = viewport.height;
 
         self.surface.configure(&self.device, &self.config);
+
+        self.msaa_target = self
+            .rendering
+            .create_msaa_target(&self.viewport, self.config.format);
+
+        // the resized target must be redrawn entirely.
+        self.dirty = DirtyRegion::full(&self.viewport);
     }
-}
 
-/// A rendering bound to a `Texture`
-pub struct TextureCompositor {
-    /// surface associated with the window.
-    texture_target: Texture,
-    /// rendeing system
-    rendering: Compositor,
-    /// GPU buffer to copy texture.
-    buffer: Buffer,
-}
+    /// Change the device-pixel-ratio of the surface's viewport, e.g. when the window moves to another monitor.
+    ///
+    /// The physical dimensions are unchanged, call [`resize`](Self::resize) if the window size changes too.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` is not a finite positive number.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.viewport = Viewport::physical(self.viewport.width, self.viewport.height, scale_factor);
+    }
 
-impl Deref for TextureCompositor {
-    type Target = Compositor;
-    fn deref(&self) -> &Self::Target {
-        &self.rendering
+    /// Returns the current present mode of the surface.
+    pub fn present_mode(&self) -> PresentMode {
+        self.config.present_mode
     }
-}
 
-impl DerefMut for TextureCompositor {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.rendering
+    /// Switch the present mode of the surface at runtime, e.g. between vsync [`Fifo`](PresentMode::Fifo)
+    /// and low-latency [`Mailbox`](PresentMode::Mailbox)/[`Immediate`](PresentMode::Immediate).
+    ///
+    /// Returns [`Error::UnsupportedPresentMode`] and keeps the current mode if the surface doesn't support `mode`.
+    pub fn set_present_mode(&mut self, mode: PresentMode) -> Result<()> {
+        let capabilities = self.surface.get_capabilities(&self.adapter);
+
+        let mode = validate_present_mode(&capabilities.present_modes, mode)?;
+
+        if self.config.present_mode != mode {
+            self.config.present_mode = mode;
+            self.surface.configure(&self.device, &self.config);
+        }
+
+        Ok(())
     }
-}
 
-impl TextureCompositor {
-    /// Invoke a rendering process and composite all rendering layer into one GPU texture.
+    /// Render a frame into an offscreen texture with the surface format and read it back, e.g. for screenshots.
     ///
-    /// On success, returns a png of rendering result.
+    /// This re-renders the whole frame instead of copying the presented one: the
+    /// [`prepare`](RenderSystem::prepare)/[`redraw`](RenderSystem::redraw)/[`composite`](RenderSystem::composite)
+    /// phases of all systems run again. The surface is not presented and [`last_stats`](Compositor::last_stats)
+    /// keeps the timing of the last [`compositing`](Self::compositing).
+    ///
+    /// Only `Rgba8*` and `Bgra8*` surface formats can be captured, others return [`Error::UnsupportedCaptureFormat`].
+    pub fn capture(&mut self) -> Result<Png<'static>> {
+        let viewport = self.viewport;
+
+        self.rendering
+            .capture(&viewport, self.msaa_target.as_ref(), SURFACE_CLEAR_COLOR)
+    }
+}
+
+/// The clear color of surface frames.
+const SURFACE_CLEAR_COLOR: Color = Color {
+    r: 1.0,
+    g: 1.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+/// Map `bounds` in normalized device coordinates to the physical pixels of `viewport`, rounded outwards.
+fn ndc_to_rect(bounds: &Box2D<f32>, viewport: &Viewport) -> Rect {
+    if bounds.is_empty() {
+        return Rect::zero();
+    }
+
+    let (width, height) = (viewport.width as f32, viewport.height as f32);
+
+    let x = |x: f32| ((x + 1.0) / 2.0 * width).clamp(0.0, width);
+    // the y axis of normalized device coordinates points up.
+    let y = |y: f32| ((1.0 - y) / 2.0 * height).clamp(0.0, height);
+
+    euclid::Box2D::new(
+        euclid::point2(
+            x(bounds.min.x).floor() as u32,
+            y(bounds.max.y).floor() as u32,
+        ),
+        euclid::point2(x(bounds.max.x).ceil() as u32, y(bounds.min.y).ceil() as u32),
+    )
+    .to_rect()
+}
+
+/// Returns `mode` if it is one of the `supported` present modes.
+///
+/// The `Auto*` modes are always supported, they fall back to [`Fifo`](PresentMode::Fifo).
+fn validate_present_mode(supported: &[PresentMode], mode: PresentMode) -> Result<PresentMode> {
+    match mode {
+        PresentMode::AutoVsync | PresentMode::AutoNoVsync => Ok(mode),
+        mode if supported.contains(&mode) => Ok(mode),
+        mode => Err(Error::UnsupportedPresentMode(mode)),
+    }
+}
+
+/// Decode the color channels of sRGB-encoded 8-bit pixels into linear values in place, alpha is kept.
+fn srgb_to_linear(image_data: &mut [u8]) {
+    // see https://www.w3.org/Graphics/Color/srgb
+    let decode = |value: u8| {
+        let c = value as f32 / 255.0;
+
+        let linear = if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        };
+
+        (linear * 255.0).round() as u8
+    };
+
+    let table: [u8; 256] = std::array::from_fn(|value| decode(value as u8));
+
+    for pixel in image_data.chunks_exact_mut(4) {
+        for channel in &mut pixel[..3] {
+            *channel = table[*channel as usize];
+        }
+    }
+}
+
+/// Swap the red and blue channels of bgra8 pixels in place.
+fn bgra_to_rgba(image_data: &mut [u8]) {
+    for pixel in image_data.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+}
+
+/// A rendering bound to a `Texture`
+pub struct TextureCompositor {
+    /// surface associated with the window.
+    texture_target: Texture,
+    /// rendeing system
+    rendering: Compositor,
+    /// GPU buffer to copy texture.
+    buffer: Buffer,
+    /// viewport of the texture target.
+    viewport: Viewport,
+    /// multisampled render target, resolved into `texture_target`.
+    msaa_target: Option<Texture>,
+    /// The region that needs to be redrawn in next frame.
+    dirty: DirtyRegion,
+}
+
+impl Deref for TextureCompositor {
+    type Target = Compositor;
+    fn deref(&self) -> &Self::Target {
+        &self.rendering
+    }
+}
+
+impl DerefMut for TextureCompositor {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rendering
+    }
+}
+
+impl TextureCompositor {
+    /// Mark a region of the texture in physical pixels as dirty.
+    ///
+    /// The next [`compositing`](Self::compositing) only redraws the union of all dirty rects,
+    /// the rest of the texture keeps the contents of the previous frame.
+    /// Canvases changed by [`set_canvas_geometry`](Compositor::set_canvas_geometry) are marked dirty automatically.
+    /// Without any dirty rect, the whole texture is redrawn.
+    pub fn mark_dirty(&mut self, rect: Rect) {
+        self.dirty.mark(rect);
+    }
+
+    /// Invoke a rendering process and composite all rendering layer into one GPU texture.
+    ///
+    /// On success, returns a png of rendering result.
     pub fn compositing(&mut self) -> Result<Png<'static>> {
+        let frame = Instant::now();
+
+        self.render_to_buffer();
+
+        let image_data = self.rendering.read_buffer(
+            &self.buffer,
+            &self.viewport,
+            self.texture_target.format(),
+        )?;
+
+        let png = to_png(image_data, self.viewport);
+
+        self.rendering.stats.frame = frame.elapsed();
+
+        png
+    }
+
+    /// Like [`compositing`](Self::compositing), but writes the raw rgba8 pixels into `image_data` instead of encoding a png.
+    ///
+    /// The content of `image_data` is replaced, its allocation is reused, so a recorder
+    /// calling this every frame with the same `Vec` doesn't allocate once the capacity is reached.
+    /// The GPU readback buffer is owned by this compositor and reused too.
+    pub fn compositing_into(&mut self, image_data: &mut Vec<u8>) -> Result<()> {
+        let frame = Instant::now();
+
+        self.render_to_buffer();
+
+        self.rendering.read_buffer_into(
+            &self.buffer,
+            &self.viewport,
+            self.texture_target.format(),
+            image_data,
+        )?;
+
+        self.rendering.stats.frame = frame.elapsed();
+
+        Ok(())
+    }
+
+    /// Render one frame and record a copy of the texture target into the readback buffer.
+    fn render_to_buffer(&mut self) {
         let mut command_encoder =
             self.rendering
                 .device
@@ -351,84 +1157,46 @@ impl TextureCompositor {
                     label: Some("TextureCompositor"),
                 });
 
-        let viewport = Viewport::new(self.texture_target.width(), self.texture_target.height());
+        let viewport = self.viewport;
 
         let texture_view = self.texture_target.create_view(&Default::default());
 
-        self.prepare(&viewport, &mut command_encoder);
-
-        {
-            let mut render_pass = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("TextureCompositor"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &texture_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.1,
-                            b: 0.1,
-                            a: 0.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
+        let msaa_view = self
+            .msaa_target
+            .as_ref()
+            .map(|texture| texture.create_view(&Default::default()));
 
-            self.redraw(&viewport, &mut render_pass);
-        }
+        self.rendering.take_redraw(&viewport, &mut self.dirty);
 
-        self.composite(&viewport, &mut command_encoder);
+        // the texture target and the msaa target are owned by this compositor, so they are always preserved.
+        let (load, scissor) = self.dirty.take_frame(&viewport, true, TEXTURE_CLEAR_COLOR);
 
-        command_encoder.copy_texture_to_buffer(
-            wgpu::ImageCopyTexture {
-                aspect: wgpu::TextureAspect::All,
-                texture: &self.texture_target,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-            },
-            wgpu::ImageCopyBuffer {
-                buffer: &self.buffer,
-                layout: wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: Some(size_of::<u32>() as u32 * self.texture_target.width()),
-                    rows_per_image: Some(self.texture_target.height()),
-                },
-            },
-            Extent3d {
-                width: self.texture_target.width(),
-                height: self.texture_target.height(),
-                depth_or_array_layers: 1,
-            },
+        self.rendering.render(
+            &viewport,
+            &mut command_encoder,
+            &texture_view,
+            msaa_view.as_ref(),
+            load,
+            scissor,
         );
 
-        self.submit([command_encoder.finish()]);
-
-        let (sender, receiver) = mpsc::channel();
-
-        self.buffer
-            .slice(..)
-            .map_async(wgpu::MapMode::Read, move |result| {
-                log::trace!("TextureCompositor, buffer map_async: {:?}", result);
-                _ = sender.send(result);
-            });
-
-        self.device.poll(wgpu::MaintainBase::Wait);
-
-        receiver.recv().unwrap()?;
-
-        let view = self.buffer.slice(..).get_mapped_range();
-        let image_data = view.to_vec();
-        drop(view);
-        self.buffer.unmap();
+        Compositor::copy_to_buffer(&mut command_encoder, &self.texture_target, &self.buffer);
 
-        to_png(image_data, viewport)
+        self.submit([command_encoder.finish()]);
     }
 }
 
+/// The clear color of texture frames.
+const TEXTURE_CLEAR_COLOR: Color = Color {
+    r: 0.1,
+    g: 0.1,
+    b: 0.1,
+    a: 0.0,
+};
+
+/// Encode the rgba8 `image_data` of `viewport` dimensions into a png.
+///
+/// Returns [`Error::PngEncodingError`] if the encoding failed, e.g. the length of `image_data` doesn't match the `viewport`.
 pub fn to_png(image_data: Vec<u8>, viewport: Viewport) -> Result<Png<'static>> {
     let mut png_data = Vec::<u8>::with_capacity(image_data.len());
     let mut encoder = png::Encoder::new(
@@ -447,8 +1215,123 @@ pub fn to_png(image_data: Vec<u8>, viewport: Viewport) -> Result<Png<'static>> {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use ecsrs::World;
+    use euclid::point2;
+
     use super::*;
 
+    /// A system that records how many times each phase was invoked.
+    #[derive(Default, Clone)]
+    struct MockSystem {
+        prepare: Arc<AtomicUsize>,
+        redraw: Arc<AtomicUsize>,
+        composite: Arc<AtomicUsize>,
+    }
+
+    impl RenderSystem for MockSystem {
+        fn prepare(&self, _: &mut World, _: &Viewport, _: &mut CommandEncoder) {
+            self.prepare.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn redraw<'a>(&self, _: &mut World, _: &Viewport, _: &mut RenderPass<'a>) {
+            self.redraw.fetch_add(1, Ordering::SeqCst);
+        }
+
+        fn composite(&self, _: &mut World, _: &Viewport, _: &mut CommandEncoder) {
+            self.composite.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_bgra_to_rgba() {
+        let mut image_data = vec![1, 2, 3, 4, 5, 6, 7, 8];
+
+        bgra_to_rgba(&mut image_data);
+
+        assert_eq!(image_data, [3, 2, 1, 4, 7, 6, 5, 8]);
+    }
+
+    #[test]
+    fn test_validate_present_mode() {
+        let supported = [PresentMode::Fifo, PresentMode::Mailbox];
+
+        assert_eq!(
+            validate_present_mode(&supported, PresentMode::Mailbox).unwrap(),
+            PresentMode::Mailbox
+        );
+
+        assert_eq!(
+            validate_present_mode(&supported, PresentMode::Fifo).unwrap(),
+            PresentMode::Fifo
+        );
+
+        assert_eq!(
+            validate_present_mode(&supported, PresentMode::AutoNoVsync).unwrap(),
+            PresentMode::AutoNoVsync
+        );
+
+        assert!(matches!(
+            validate_present_mode(&supported, PresentMode::Immediate),
+            Err(Error::UnsupportedPresentMode(PresentMode::Immediate))
+        ));
+    }
+
+    #[test]
+    fn test_dirty_region() {
+        let viewport = Viewport::new(100, 100);
+
+        let mut dirty = DirtyRegion::default();
+
+        assert_eq!(dirty.take_scissor(&viewport), None);
+
+        dirty.mark(Rect::new(euclid::point2(10, 10), euclid::size2(10, 10)));
+        dirty.mark(Rect::new(euclid::point2(30, 5), euclid::size2(10, 10)));
+
+        assert_eq!(
+            dirty.take_scissor(&viewport),
+            Some(Rect::new(euclid::point2(10, 5), euclid::size2(30, 15)))
+        );
+
+        // reset after taken.
+        assert_eq!(dirty.take_scissor(&viewport), None);
+
+        // clamped to the viewport.
+        dirty.mark(Rect::new(euclid::point2(90, 90), euclid::size2(20, 20)));
+
+        assert_eq!(
+            dirty.take_scissor(&viewport),
+            Some(Rect::new(euclid::point2(90, 90), euclid::size2(10, 10)))
+        );
+
+        // whole viewport, full redraw.
+        dirty.mark(Rect::new(euclid::point2(0, 0), euclid::size2(200, 200)));
+
+        assert_eq!(dirty.take_scissor(&viewport), None);
+
+        // a target that is not preserved between frames is always cleared and redrawn entirely.
+        dirty.mark(Rect::new(euclid::point2(10, 10), euclid::size2(10, 10)));
+
+        assert_eq!(
+            dirty.take_frame(&viewport, false, SURFACE_CLEAR_COLOR),
+            (LoadOp::Clear(SURFACE_CLEAR_COLOR), None)
+        );
+
+        dirty.mark(Rect::new(euclid::point2(10, 10), euclid::size2(10, 10)));
+
+        assert_eq!(
+            dirty.take_frame(&viewport, true, SURFACE_CLEAR_COLOR),
+            (
+                LoadOp::Load,
+                Some(Rect::new(euclid::point2(10, 10), euclid::size2(10, 10)))
+            )
+        );
+    }
+
     #[futures_test::test]
     async fn test_render_to_texture() {
         let mut compositor = Compositor::new()
@@ -460,4 +1343,485 @@ mod tests {
 
         compositor.compositing().unwrap();
     }
+
+    #[futures_test::test]
+    async fn test_compositing_into() {
+        let viewport = Viewport::new(65, 3);
+
+        let mut compositor = Compositor::new().render_to_texture(viewport).await.unwrap();
+
+        let mut image_data = vec![];
+
+        compositor.compositing_into(&mut image_data).unwrap();
+
+        assert_eq!(image_data.len(), 65 * 3 * 4);
+
+        let capacity = image_data.capacity();
+
+        compositor.compositing_into(&mut image_data).unwrap();
+
+        assert_eq!(image_data.len(), 65 * 3 * 4);
+
+        // neither the caller's `Vec` nor the GPU readback buffer is reallocated.
+        assert_eq!(image_data.capacity(), capacity);
+        assert_eq!(compositor.readback_buffers(), 1);
+
+        compositor.compositing().unwrap();
+
+        assert_eq!(compositor.readback_buffers(), 1);
+    }
+
+    #[test]
+    fn test_srgb_to_linear() {
+        // sRGB mid-gray 188 is ~50% linear, alpha is kept.
+        let mut image_data = vec![0, 188, 255, 188];
+
+        srgb_to_linear(&mut image_data);
+
+        assert_eq!(image_data, [0, 128, 255, 188]);
+    }
+
+    #[futures_test::test]
+    async fn test_color_space() {
+        let capture = |color_space| async move {
+            let mut compositor = Compositor::new()
+                .color_space(color_space)
+                .render_to_texture(Viewport::new(4, 4))
+                .await
+                .unwrap();
+
+            let mut image_data = vec![];
+
+            compositor.compositing_into(&mut image_data).unwrap();
+
+            image_data
+        };
+
+        // the clear color is linear 0.1 gray.
+        let srgb = capture(ColorSpace::Srgb).await;
+        let linear = capture(ColorSpace::Linear).await;
+
+        for (srgb, linear) in srgb.chunks_exact(4).zip(linear.chunks_exact(4)) {
+            assert!(srgb[0].abs_diff(89) <= 1, "{}", srgb[0]);
+            assert!(linear[0].abs_diff(26) <= 1, "{}", linear[0]);
+            assert_eq!(srgb[3], linear[3]);
+        }
+    }
+
+    #[futures_test::test]
+    async fn test_canvas_system() {
+        let mut compositor = Compositor::new()
+            .render_to_texture(Viewport::new(16, 16))
+            .await
+            .unwrap();
+
+        let canvas = compositor.new_canvas();
+
+        let vertex = |x, y| Canvas2DVertex {
+            position: [x, y, 0.0],
+            color: [1.0, 0.0, 0.0],
+        };
+
+        // a triangle covers the whole viewport.
+        compositor.set_canvas_geometry(
+            &canvas,
+            &[vertex(-1.0, -1.0), vertex(3.0, -1.0), vertex(-1.0, 3.0)],
+            &[0, 1, 2],
+        );
+
+        let mut image_data = vec![];
+
+        compositor.compositing_into(&mut image_data).unwrap();
+
+        assert!(image_data
+            .chunks_exact(4)
+            .all(|pixel| pixel == [255, 0, 0, 255]));
+
+        // replace with an empty geometry.
+        compositor.set_canvas_geometry(&canvas, &[vertex(0.0, 0.0)], &[]);
+
+        compositor.compositing_into(&mut image_data).unwrap();
+
+        assert!(image_data.chunks_exact(4).all(|pixel| pixel[0] != 255));
+    }
+
+    #[test]
+    fn test_ndc_to_rect() {
+        let viewport = Viewport::new(16, 16);
+
+        assert_eq!(
+            ndc_to_rect(&Box2D::new(point2(-1.0, -1.0), point2(0.0, 1.0)), &viewport),
+            Rect::new(euclid::point2(0, 0), euclid::size2(8, 16))
+        );
+
+        // the y axis points up, bounds are rounded outwards and clamped to the viewport.
+        assert_eq!(
+            ndc_to_rect(&Box2D::new(point2(-0.1, 0.5), point2(3.0, 3.0)), &viewport),
+            Rect::new(euclid::point2(7, 0), euclid::size2(9, 4))
+        );
+
+        assert_eq!(ndc_to_rect(&Box2D::zero(), &viewport), Rect::zero());
+    }
+
+    #[futures_test::test]
+    async fn test_partial_redraw() {
+        let mut compositor = Compositor::new()
+            .render_to_texture(Viewport::new(16, 16))
+            .await
+            .unwrap();
+
+        let left = compositor.new_canvas();
+        let right = compositor.new_canvas();
+
+        // fill the horizontal range `x0..x1` of the texture.
+        let set_color = |compositor: &mut TextureCompositor, canvas: &Id, x0, x1, color| {
+            let vertex = |x, y| Canvas2DVertex {
+                position: [x, y, 0.0],
+                color,
+            };
+
+            compositor.set_canvas_geometry(
+                canvas,
+                &[
+                    vertex(x0, -1.0),
+                    vertex(x1, -1.0),
+                    vertex(x1, 1.0),
+                    vertex(x0, 1.0),
+                ],
+                &[0, 1, 2, 0, 2, 3],
+            );
+        };
+
+        set_color(&mut compositor, &left, -1.0, 0.0, [1.0, 0.0, 0.0]);
+        set_color(&mut compositor, &right, 0.0, 1.0, [1.0, 0.0, 0.0]);
+
+        let mut image_data = vec![];
+
+        compositor.compositing_into(&mut image_data).unwrap();
+
+        assert!(image_data
+            .chunks_exact(4)
+            .all(|pixel| pixel == [255, 0, 0, 255]));
+
+        // drop the right canvas from the redraw list, so that its change is not redrawn.
+        set_color(&mut compositor, &right, 0.0, 1.0, [0.0, 1.0, 0.0]);
+
+        let redraw = compositor.redraw;
+
+        compositor
+            .world
+            .component_mut::<RedrawComponent>(&redraw)
+            .unwrap()
+            .0
+            .clear();
+
+        // only the region of the left canvas is redrawn.
+        set_color(&mut compositor, &left, -1.0, 0.0, [0.0, 1.0, 0.0]);
+
+        compositor.compositing_into(&mut image_data).unwrap();
+
+        for (index, pixel) in image_data.chunks_exact(4).enumerate() {
+            if index % 16 < 8 {
+                assert_eq!(pixel, [0, 255, 0, 255], "pixel {}", index);
+            } else {
+                assert_eq!(pixel, [255, 0, 0, 255], "pixel {}", index);
+            }
+        }
+
+        // without dirty rects, the whole texture is redrawn.
+        compositor.compositing_into(&mut image_data).unwrap();
+
+        assert!(image_data
+            .chunks_exact(4)
+            .all(|pixel| pixel == [0, 255, 0, 255]));
+    }
+
+    #[futures_test::test]
+    async fn test_capture() {
+        let viewport = Viewport::new(4, 4);
+
+        let (device, queue, adapter) = CompositorBuilder::create_wgpu(PowerPreference::default())
+            .await
+            .unwrap();
+
+        let mut compositor = Compositor::new()
+            .create(device, queue, &adapter, TextureFormat::Bgra8UnormSrgb)
+            .await
+            .unwrap();
+
+        let canvas = compositor.new_canvas();
+
+        let vertex = |x, y| Canvas2DVertex {
+            position: [x, y, 0.0],
+            color: [1.0, 0.0, 0.0],
+        };
+
+        compositor.set_canvas_geometry(
+            &canvas,
+            &[vertex(-1.0, -1.0), vertex(3.0, -1.0), vertex(-1.0, 3.0)],
+            &[0, 1, 2],
+        );
+
+        let stats = CompositorStats {
+            frame: Duration::from_secs(1),
+            ..Default::default()
+        };
+
+        compositor.stats = stats;
+
+        let Png::Data(data) = compositor
+            .capture(&viewport, None, SURFACE_CLEAR_COLOR)
+            .unwrap()
+        else {
+            panic!("expect png data");
+        };
+
+        // the bgra pixels are swizzled into rgba.
+        let mut reader = png::Decoder::new(std::io::Cursor::new(data))
+            .read_info()
+            .unwrap();
+
+        let mut image_data = vec![0; reader.output_buffer_size()];
+
+        reader.next_frame(&mut image_data).unwrap();
+
+        assert!(image_data
+            .chunks_exact(4)
+            .all(|pixel| pixel == [255, 0, 0, 255]));
+
+        assert_eq!(compositor.last_stats(), stats);
+
+        let (device, queue, adapter) = CompositorBuilder::create_wgpu(PowerPreference::default())
+            .await
+            .unwrap();
+
+        let mut compositor = Compositor::new()
+            .create(device, queue, &adapter, TextureFormat::Rgba16Float)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            compositor.capture(&viewport, None, SURFACE_CLEAR_COLOR),
+            Err(Error::UnsupportedCaptureFormat(TextureFormat::Rgba16Float))
+        ));
+    }
+
+    #[futures_test::test]
+    async fn test_power_preference() {
+        let mut compositor = Compositor::new()
+            .power_preference(PowerPreference::HighPerformance)
+            .render_to_texture(Viewport::new(16, 16))
+            .await
+            .unwrap();
+
+        compositor.compositing().unwrap();
+    }
+
+    #[futures_test::test]
+    async fn test_to_png() {
+        let mut compositor = Compositor::new()
+            .render_to_texture(Viewport::new(128, 64))
+            .await
+            .unwrap();
+
+        let Png::Data(data) = compositor.compositing().unwrap() else {
+            panic!("expect png data");
+        };
+
+        let reader = png::Decoder::new(std::io::Cursor::new(data))
+            .read_info()
+            .unwrap();
+
+        assert_eq!(reader.info().width, 128);
+        assert_eq!(reader.info().height, 64);
+        assert_eq!(reader.info().color_type, png::ColorType::Rgba);
+
+        assert!(matches!(
+            to_png(vec![0; 3], Viewport::new(2, 2)),
+            Err(Error::PngEncodingError(_))
+        ));
+    }
+
+    #[futures_test::test]
+    async fn test_render_to_unaligned_texture() {
+        let mut compositor = Compositor::new()
+            .render_to_texture(Viewport::new(65, 3))
+            .await
+            .unwrap();
+
+        let Png::Data(data) = compositor.compositing().unwrap() else {
+            panic!("expect png data");
+        };
+
+        let reader = png::Decoder::new(std::io::Cursor::new(data))
+            .read_info()
+            .unwrap();
+
+        assert_eq!(reader.info().width, 65);
+        assert_eq!(reader.info().height, 3);
+    }
+
+    #[futures_test::test]
+    async fn test_render_to_texture_with_scale() {
+        let mut compositor = Compositor::new()
+            .render_to_texture(Viewport::with_scale(128, 64, 2.0))
+            .await
+            .unwrap();
+
+        assert_eq!(compositor.texture_target.width(), 256);
+        assert_eq!(compositor.texture_target.height(), 128);
+        assert_eq!(compositor.viewport.logical_width(), 128);
+        assert_eq!(compositor.viewport.logical_height(), 64);
+
+        compositor.compositing().unwrap();
+    }
+
+    #[futures_test::test]
+    async fn test_with_system() {
+        struct MockComponent;
+
+        static MOCK_COMPONENT: ComponentType = ComponentType::new(100);
+
+        let system = MockSystem::default();
+
+        let mut compositor = Compositor::new()
+            .with_system(Box::new(system.clone()), [&MOCK_COMPONENT])
+            .render_to_texture(Viewport::new(256, 256))
+            .await
+            .unwrap();
+
+        let entity = compositor.world.new_entity();
+
+        compositor
+            .world
+            .new_component_with(&MOCK_COMPONENT, MockComponent, [&entity]);
+
+        compositor.compositing().unwrap();
+
+        assert!(system.prepare.load(Ordering::SeqCst) > 0);
+        assert!(system.redraw.load(Ordering::SeqCst) > 0);
+        assert!(system.composite.load(Ordering::SeqCst) > 0);
+    }
+
+    #[futures_test::test]
+    async fn test_system_phases() {
+        let system = MockSystem::default();
+
+        let mut compositor = Compositor::new()
+            .with_system(Box::new(system.clone()), [] as [ComponentType; 0])
+            .render_to_texture(Viewport::new(256, 256))
+            .await
+            .unwrap();
+
+        assert_eq!(compositor.last_stats(), CompositorStats::default());
+
+        for i in 1..3 {
+            compositor.compositing().unwrap();
+
+            let stats = compositor.last_stats();
+
+            assert!(stats.frame > Duration::ZERO);
+            assert!(stats.frame >= stats.prepare + stats.redraw + stats.composite + stats.submit);
+
+            assert_eq!(system.prepare.load(Ordering::SeqCst), i);
+            assert_eq!(system.redraw.load(Ordering::SeqCst), i);
+            assert_eq!(system.composite.load(Ordering::SeqCst), i);
+        }
+    }
+
+    #[futures_test::test]
+    async fn test_msaa() {
+        let mut compositor = Compositor::new()
+            .sample_count(4)
+            .render_to_texture(Viewport::new(256, 256))
+            .await
+            .unwrap();
+
+        assert_eq!(compositor.sample_count, 4);
+
+        assert_eq!(
+            compositor
+                .msaa_target
+                .as_ref()
+                .map(|texture| texture.sample_count()),
+            Some(4)
+        );
+
+        compositor.compositing().unwrap();
+    }
+
+    #[futures_test::test]
+    async fn test_msaa_antialiasing() {
+        // counts the pixels on the diagonal edge of a triangle, which are neither the clear color nor red.
+        let edge_pixels = |sample_count| async move {
+            let mut compositor = Compositor::new()
+                .sample_count(sample_count)
+                .render_to_texture(Viewport::new(64, 64))
+                .await
+                .unwrap();
+
+            let canvas = compositor.new_canvas();
+
+            let vertex = |x, y| Canvas2DVertex {
+                position: [x, y, 0.0],
+                color: [1.0, 0.0, 0.0],
+            };
+
+            compositor.set_canvas_geometry(
+                &canvas,
+                &[vertex(-1.0, -1.0), vertex(1.0, -1.0), vertex(-1.0, 1.0)],
+                &[0, 1, 2],
+            );
+
+            let mut image_data = vec![];
+
+            compositor.compositing_into(&mut image_data).unwrap();
+
+            let clear = image_data[(64 * 64 - 1) * 4];
+
+            image_data
+                .chunks_exact(4)
+                .filter(|pixel| pixel[0] != clear && pixel[0] != 255)
+                .count()
+        };
+
+        assert_eq!(edge_pixels(1).await, 0);
+        assert!(edge_pixels(4).await > 0);
+    }
+
+    #[futures_test::test]
+    async fn test_with_system_factory() {
+        let (sender, receiver) = mpsc::channel();
+
+        let compositor = Compositor::new()
+            .sample_count(3)
+            .with_system_factory(
+                Box::new(move |device, format, sample_count| {
+                    sender.send((format, sample_count)).unwrap();
+                    Box::new(CanvasSystem::new(None, device, format, sample_count))
+                }),
+                None::<ComponentType>,
+            )
+            .render_to_texture(Viewport::new(16, 16))
+            .await
+            .unwrap();
+
+        assert_eq!(compositor.sample_count(), 1);
+        assert_eq!(compositor.format(), TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(receiver.recv().unwrap(), (TextureFormat::Rgba8UnormSrgb, 1));
+    }
+
+    #[futures_test::test]
+    async fn test_msaa_fallback() {
+        let mut compositor = Compositor::new()
+            .sample_count(3)
+            .render_to_texture(Viewport::new(256, 256))
+            .await
+            .unwrap();
+
+        assert_eq!(compositor.sample_count, 1);
+        assert!(compositor.msaa_target.is_none());
+
+        compositor.compositing().unwrap();
+    }
 }
@@ -1,6 +1,6 @@
-use wgpu::{Device, RenderPipeline, RenderPipelineDescriptor, ShaderSource};
+use wgpu::{Device, RenderPipeline, RenderPipelineDescriptor, ShaderSource, TextureFormat};
 
-use crate::compositor::{Canvas2DComponent, Canvas2DVertex};
+use crate::compositor::{Canvas2DComponent, Canvas2DGeometryComponent, Canvas2DVertex};
 
 use super::RenderSystem;
 
@@ -16,16 +16,24 @@ pub struct SvgSystem {
 
 impl SvgSystem {
     /// Create new [`SvgSystem`] with default shader.
-    pub fn new(label: Option<&str>, device: &Device) -> Self {
+    ///
+    /// `sample_count` must match the msaa sample count of the render target.
+    pub fn new(label: Option<&str>, device: &Device, sample_count: u32) -> Self {
         Self::with_shader(
             label,
             device,
             ShaderSource::Wgsl(include_str!("./shader/svg.wgsl").into()),
+            sample_count,
         )
     }
 
     /// Create [`SvgSystem`] with provided [`ShaderSource`]
-    pub fn with_shader(label: Option<&str>, device: &Device, shader_source: ShaderSource) -> Self {
+    pub fn with_shader(
+        label: Option<&str>,
+        device: &Device,
+        shader_source: ShaderSource,
+        sample_count: u32,
+    ) -> Self {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("CanvasLayer"),
             source: shader_source,
@@ -60,7 +68,10 @@ impl SvgSystem {
                 conservative: false,
             },
             depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
             multiview: None,
             cache: None,
         });
@@ -100,3 +111,106 @@ impl RenderSystem for SvgSystem {
     ) {
     }
 }
+
+/// A system that draws the [`Canvas2DGeometryComponent`] of canvases into the viewport.
+pub struct CanvasSystem {
+    /// The debug label for this system.
+    #[allow(unused)]
+    label: Option<String>,
+
+    /// Render pipeline of this system.
+    render_pipeline: RenderPipeline,
+}
+
+impl CanvasSystem {
+    /// Create new [`CanvasSystem`] with default shader.
+    ///
+    /// `format` and `sample_count` must match the render target.
+    pub fn new(
+        label: Option<&str>,
+        device: &Device,
+        format: TextureFormat,
+        sample_count: u32,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("CanvasSystem"),
+            source: ShaderSource::Wgsl(include_str!("./shader/svg.wgsl").into()),
+        });
+
+        let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
+            label,
+            layout: None,
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                compilation_options: Default::default(),
+                buffers: &[Canvas2DVertex::vertex_buff_layout()],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                compilation_options: Default::default(),
+                targets: &[Some(format.into())],
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: Some(wgpu::Face::Back),
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: sample_count,
+                ..Default::default()
+            },
+            multiview: None,
+            cache: None,
+        });
+
+        CanvasSystem {
+            label: label.map(|label| label.to_owned()),
+            render_pipeline,
+        }
+    }
+}
+
+impl RenderSystem for CanvasSystem {
+    fn prepare(
+        &self,
+        _world: &mut ecsrs::World,
+        _viewport: &crate::Viewport,
+        _command_encoder: &mut wgpu::CommandEncoder,
+    ) {
+    }
+
+    fn redraw<'a>(
+        &self,
+        world: &mut ecsrs::World,
+        _viewport: &crate::Viewport,
+        render_pass: &mut wgpu::RenderPass<'a>,
+    ) {
+        render_pass.set_pipeline(&self.render_pipeline);
+
+        for geometry in world.component_iter::<Canvas2DGeometryComponent>() {
+            if geometry.index_count == 0 {
+                continue;
+            }
+
+            render_pass.set_vertex_buffer(0, geometry.vertex_buffer.slice(..));
+            render_pass
+                .set_index_buffer(geometry.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..geometry.index_count, 0, 0..1);
+        }
+    }
+
+    fn composite(
+        &self,
+        _world: &mut ecsrs::World,
+        _viewport: &crate::Viewport,
+        _command_encoder: &mut wgpu::CommandEncoder,
+    ) {
+    }
+}
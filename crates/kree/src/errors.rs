@@ -1,5 +1,8 @@
 use png::EncodingError;
-use wgpu::{BufferAsyncError, CreateSurfaceError, RequestDeviceError, SurfaceError};
+use wgpu::{
+    BufferAsyncError, CreateSurfaceError, PresentMode, RequestDeviceError, SurfaceError,
+    TextureFormat,
+};
 
 /// The error type used by this crate.
 #[derive(Debug, thiserror::Error)]
@@ -36,6 +39,14 @@ pub enum Error {
 
     #[error("Unknown color: {0}")]
     UnrecognizedColor(String),
+
+    /// The render target format can't be read back as a rgba8 image.
+    #[error("Unsupported capture format: {0:?}")]
+    UnsupportedCaptureFormat(TextureFormat),
+
+    /// The surface doesn't support the requested present mode.
+    #[error("Unsupported present mode: {0:?}")]
+    UnsupportedPresentMode(PresentMode),
 }
 
 /// The result type used by this crate.
@@ -13,14 +13,36 @@ pub type Transform3D = euclid::default::Transform3D<f32>;
 /// Unit pixels.
 pub struct Pixels;
 
+/// A rectangle in physical pixels.
+pub type Rect = euclid::Rect<u32, Pixels>;
+
+/// The color space of captured pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// sRGB-encoded values, the bytes of the render target are passed through (the default).
+    #[default]
+    Srgb,
+    /// Linear values, sRGB-encoded render targets are decoded with the sRGB transfer function on readback.
+    Linear,
+}
+
 /// Viewport dimensions
+///
+/// The dereferenced `width`/`height` are the physical pixel dimensions of the render target,
+/// use [`logical_width`](Viewport::logical_width)/[`logical_height`](Viewport::logical_height)
+/// to get the dimensions before applying the device-pixel-ratio.
 #[derive(Debug, Clone, Copy)]
-pub struct Viewport(euclid::Size2D<u32, Pixels>);
+pub struct Viewport {
+    /// physical pixel dimensions.
+    size: euclid::Size2D<u32, Pixels>,
+    /// device-pixel-ratio.
+    scale_factor: f32,
+}
 
 impl Deref for Viewport {
     type Target = euclid::Size2D<u32, Pixels>;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.size
     }
 }
 
@@ -31,8 +53,73 @@ impl Display for Viewport {
 }
 
 impl Viewport {
+    /// Create a viewport with the default scale factor `1.0`.
     pub fn new(width: u32, height: u32) -> Self {
-        Self(euclid::Size2D::new(width, height))
+        Self::with_scale(width, height, 1.0)
+    }
+
+    /// Create a viewport from logical dimensions and a device-pixel-ratio.
+    ///
+    /// The physical dimensions are `logical * scale_factor`, rounded to the nearest pixel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` is not a finite positive number.
+    pub fn with_scale(width: u32, height: u32, scale_factor: f32) -> Self {
+        Self::physical(
+            (width as f32 * scale_factor).round() as u32,
+            (height as f32 * scale_factor).round() as u32,
+            scale_factor,
+        )
+    }
+
+    /// Create a viewport from physical pixel dimensions and a device-pixel-ratio,
+    /// e.g. from a window's `PhysicalSize` and `scale_factor`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `scale_factor` is not a finite positive number.
+    pub fn physical(width: u32, height: u32, scale_factor: f32) -> Self {
+        assert!(
+            scale_factor.is_finite() && scale_factor > 0.0,
+            "Viewport: scale factor must be finite and positive, got {}",
+            scale_factor
+        );
+
+        Self {
+            size: euclid::Size2D::new(width, height),
+            scale_factor,
+        }
+    }
+
+    /// Returns the device-pixel-ratio of this viewport.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Returns the width before applying the device-pixel-ratio.
+    pub fn logical_width(&self) -> u32 {
+        (self.width as f32 / self.scale_factor).round() as u32
+    }
+
+    /// Returns the height before applying the device-pixel-ratio.
+    pub fn logical_height(&self) -> u32 {
+        (self.height as f32 / self.scale_factor).round() as u32
+    }
+
+    /// Returns the physical pixel bounds of this viewport, the origin is `(0, 0)`.
+    pub fn as_rect(&self) -> Rect {
+        Rect::from_size(self.size)
+    }
+
+    /// Returns true if the physical pixel `(x, y)` is inside this viewport.
+    pub fn contains_point(&self, x: u32, y: u32) -> bool {
+        self.as_rect().contains(euclid::Point2D::new(x, y))
+    }
+
+    /// Clamp `rect` to the bounds of this viewport, returns an empty rect if they don't intersect.
+    pub fn clamp_rect(&self, rect: Rect) -> Rect {
+        rect.intersection(&self.as_rect()).unwrap_or(Rect::zero())
     }
 }
 
@@ -58,14 +145,25 @@ impl FromStr for Viewport {
     }
 }
 
+/// Returns the `bytes_per_row` of a rgba8 texture copy with `width` pixels,
+/// rounded up to [`COPY_BYTES_PER_ROW_ALIGNMENT`](wgpu::COPY_BYTES_PER_ROW_ALIGNMENT).
+pub fn aligned_bytes_per_row(width: u32) -> u32 {
+    let bytes_per_row = size_of::<u32>() as u32 * width;
+
+    bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT
+}
+
 /// Calculates the size of the buffer used to hold the render target with `viewport` dimensions.
+///
+/// Each row is padded to [`aligned_bytes_per_row`].
 pub trait BufferSizeOf {
     fn buffer_size_of(&self) -> u64;
 }
 
 impl BufferSizeOf for Viewport {
     fn buffer_size_of(&self) -> u64 {
-        (size_of::<u32>() as u32 * self.width * self.height) as wgpu::BufferAddress
+        aligned_bytes_per_row(self.width) as wgpu::BufferAddress
+            * self.height as wgpu::BufferAddress
     }
 }
 
@@ -97,3 +195,60 @@ impl<'a> Png<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_size_of() {
+        assert_eq!(aligned_bytes_per_row(64), 256);
+        assert_eq!(aligned_bytes_per_row(65), 512);
+        assert_eq!(aligned_bytes_per_row(1), 256);
+
+        assert_eq!(Viewport::new(64, 2).buffer_size_of(), 512);
+        assert_eq!(Viewport::new(65, 2).buffer_size_of(), 1024);
+    }
+
+    #[test]
+    fn test_viewport_scale() {
+        let viewport = Viewport::with_scale(500, 300, 2.0);
+
+        assert_eq!((viewport.width, viewport.height), (1000, 600));
+        assert_eq!(viewport.logical_width(), 500);
+
+        let viewport = Viewport::physical(1001, 601, 2.0);
+
+        assert_eq!((viewport.width, viewport.height), (1001, 601));
+        assert_eq!(viewport.scale_factor(), 2.0);
+
+        std::panic::catch_unwind(|| Viewport::with_scale(1, 1, 0.0)).expect_err("zero scale");
+        std::panic::catch_unwind(|| Viewport::physical(1, 1, f32::NAN)).expect_err("nan scale");
+        std::panic::catch_unwind(|| Viewport::physical(1, 1, f32::INFINITY))
+            .expect_err("infinite scale");
+    }
+
+    #[test]
+    fn test_viewport_bounds() {
+        let viewport = Viewport::new(100, 50);
+
+        assert_eq!(
+            viewport.as_rect(),
+            Rect::new((0, 0).into(), (100, 50).into())
+        );
+
+        assert!(viewport.contains_point(0, 0));
+        assert!(viewport.contains_point(99, 49));
+        assert!(!viewport.contains_point(100, 10));
+        assert!(!viewport.contains_point(10, 50));
+
+        assert_eq!(
+            viewport.clamp_rect(Rect::new((90, 40).into(), (200, 200).into())),
+            Rect::new((90, 40).into(), (10, 10).into())
+        );
+
+        assert!(viewport
+            .clamp_rect(Rect::new((100, 0).into(), (10, 10).into()))
+            .is_empty());
+    }
+}